@@ -0,0 +1,503 @@
+use bevy::prelude::*;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::daily::today;
+use crate::models::Card;
+use crate::resources::{Difficulty, GameAssets, GameState, SpiderGame};
+use crate::stats::Statistics;
+use crate::storage;
+use crate::systems::{MoveAppliedEvent, SuitCompletedEvent};
+use crate::theme::ThemeBackground;
+
+pub const ACHIEVEMENTS_FILE: &str = "achievements.json";
+const FEW_MOVES: u32 = 150;
+const FAST_WIN_SECS: f32 = 600.0;
+const STREAK_LENGTH: usize = 5;
+const FULL_STOCK: usize = 50;
+const TOAST_SECS: f32 = 4.0;
+
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(storage::load::<Achievements>(ACHIEVEMENTS_FILE))
+            .add_message::<AchievementUnlocked>()
+            .add_systems(Startup, setup_toast_area)
+            .add_systems(
+                Update,
+                (
+                    evaluate_game_events,
+                    save_achievements,
+                    spawn_toasts,
+                    expire_toasts,
+                )
+                    .chain(),
+            )
+            .add_systems(OnEnter(GameState::Won), evaluate_win)
+            .add_systems(OnEnter(GameState::Achievements), setup_achievements_screen)
+            .add_systems(
+                Update,
+                handle_achievements_interaction.run_if(in_state(GameState::Achievements)),
+            )
+            .add_systems(OnExit(GameState::Achievements), cleanup_achievements_screen);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AchievementId {
+    FirstWinOneSuit,
+    FirstWinTwoSuits,
+    FirstWinFourSuits,
+    NoUndoWin,
+    FewMovesWin,
+    FastWin,
+    FullStockSuit,
+    ColumnUncovered,
+    WinStreak,
+}
+
+impl AchievementId {
+    pub fn all() -> [AchievementId; 9] {
+        [
+            AchievementId::FirstWinOneSuit,
+            AchievementId::FirstWinTwoSuits,
+            AchievementId::FirstWinFourSuits,
+            AchievementId::NoUndoWin,
+            AchievementId::FewMovesWin,
+            AchievementId::FastWin,
+            AchievementId::FullStockSuit,
+            AchievementId::ColumnUncovered,
+            AchievementId::WinStreak,
+        ]
+    }
+
+    pub fn title(&self) -> &str {
+        match self {
+            AchievementId::FirstWinOneSuit => "Spinner",
+            AchievementId::FirstWinTwoSuits => "Weaver",
+            AchievementId::FirstWinFourSuits => "Black Widow",
+            AchievementId::NoUndoWin => "No Regrets",
+            AchievementId::FewMovesWin => "Economist",
+            AchievementId::FastWin => "Quick Silk",
+            AchievementId::FullStockSuit => "Early Bird",
+            AchievementId::ColumnUncovered => "Daylight",
+            AchievementId::WinStreak => "On a Roll",
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            AchievementId::FirstWinOneSuit => "Win a one-suit game".to_string(),
+            AchievementId::FirstWinTwoSuits => "Win a two-suit game".to_string(),
+            AchievementId::FirstWinFourSuits => "Win a four-suit game".to_string(),
+            AchievementId::NoUndoWin => "Win a game without using undo".to_string(),
+            AchievementId::FewMovesWin => format!("Win a game in under {} moves", FEW_MOVES),
+            AchievementId::FastWin => {
+                format!("Win a game in under {} minutes", FAST_WIN_SECS as u32 / 60)
+            }
+            AchievementId::FullStockSuit => {
+                "Complete a suit before dealing from the stock".to_string()
+            }
+            AchievementId::ColumnUncovered => {
+                "Turn over every face-down card in a column".to_string()
+            }
+            AchievementId::WinStreak => format!("Win {} games in a row", STREAK_LENGTH),
+        }
+    }
+
+    fn first_win(difficulty: Difficulty) -> AchievementId {
+        match difficulty {
+            Difficulty::Easy => AchievementId::FirstWinOneSuit,
+            Difficulty::Medium => AchievementId::FirstWinTwoSuits,
+            Difficulty::Hard => AchievementId::FirstWinFourSuits,
+        }
+    }
+}
+
+#[derive(Message)]
+pub struct AchievementUnlocked(pub AchievementId);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UnlockedAchievement {
+    id: AchievementId,
+    date: NaiveDate,
+}
+
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Achievements {
+    unlocked: Vec<UnlockedAchievement>,
+}
+
+impl Achievements {
+    pub fn unlocked_on(&self, id: AchievementId) -> Option<NaiveDate> {
+        self.unlocked.iter().find(|u| u.id == id).map(|u| u.date)
+    }
+
+    fn unlock(&mut self, ids: impl IntoIterator<Item = AchievementId>) -> Vec<AchievementId> {
+        let mut unlocked = Vec::new();
+        for id in ids {
+            if self.unlocked_on(id).is_none() {
+                self.unlocked
+                    .push(UnlockedAchievement { id, date: today() });
+                unlocked.push(id);
+            }
+        }
+        unlocked
+    }
+
+    pub fn record_reveals(
+        &mut self,
+        hidden_before: &[usize],
+        game: &SpiderGame,
+    ) -> Vec<AchievementId> {
+        let uncovered = game
+            .tableau
+            .iter()
+            .zip(hidden_before)
+            .any(|(column, &hidden)| hidden > 0 && hidden_cards(column) == 0 && !column.is_empty());
+        self.unlock(uncovered.then_some(AchievementId::ColumnUncovered))
+    }
+
+    pub fn record_suit(&mut self, stock_remaining: usize) -> Vec<AchievementId> {
+        self.unlock((stock_remaining == FULL_STOCK).then_some(AchievementId::FullStockSuit))
+    }
+
+    pub fn record_win(&mut self, game: &SpiderGame, stats: &Statistics) -> Vec<AchievementId> {
+        let streak = stats.records.iter().rev().take_while(|r| r.won).count();
+        self.unlock(
+            [
+                Some(AchievementId::first_win(game.difficulty)),
+                (game.undo_count == 0).then_some(AchievementId::NoUndoWin),
+                (game.move_count < FEW_MOVES).then_some(AchievementId::FewMovesWin),
+                (game.elapsed_secs < FAST_WIN_SECS).then_some(AchievementId::FastWin),
+                (streak >= STREAK_LENGTH).then_some(AchievementId::WinStreak),
+            ]
+            .into_iter()
+            .flatten(),
+        )
+    }
+}
+
+fn hidden_cards(column: &[Card]) -> usize {
+    column.iter().filter(|card| !card.face_up).count()
+}
+
+pub fn hidden_counts(game: &SpiderGame) -> Vec<usize> {
+    game.tableau
+        .iter()
+        .map(|column| hidden_cards(column))
+        .collect()
+}
+
+fn announce(
+    achievements: &mut ResMut<Achievements>,
+    unlocked: Vec<AchievementId>,
+    writer: &mut MessageWriter<AchievementUnlocked>,
+) {
+    if !unlocked.is_empty() {
+        achievements.set_changed();
+    }
+    for id in unlocked {
+        writer.write(AchievementUnlocked(id));
+    }
+}
+
+fn evaluate_game_events(
+    game: Res<SpiderGame>,
+    mut moves: MessageReader<MoveAppliedEvent>,
+    mut suits: MessageReader<SuitCompletedEvent>,
+    mut achievements: ResMut<Achievements>,
+    mut writer: MessageWriter<AchievementUnlocked>,
+    mut hidden_before: Local<Vec<usize>>,
+) {
+    let moved = moves.read().count() > 0;
+    let completed: Vec<usize> = suits.read().map(|ev| ev.stock_remaining).collect();
+    let mut unlocked = Vec::new();
+    for stock_remaining in &completed {
+        unlocked.extend(
+            achievements
+                .bypass_change_detection()
+                .record_suit(*stock_remaining),
+        );
+    }
+    if moved || !completed.is_empty() {
+        unlocked.extend(
+            achievements
+                .bypass_change_detection()
+                .record_reveals(&hidden_before, &game),
+        );
+    }
+    *hidden_before = hidden_counts(&game);
+    announce(&mut achievements, unlocked, &mut writer);
+}
+
+fn evaluate_win(
+    game: Res<SpiderGame>,
+    stats: Res<Statistics>,
+    mut achievements: ResMut<Achievements>,
+    mut writer: MessageWriter<AchievementUnlocked>,
+) {
+    let unlocked = achievements
+        .bypass_change_detection()
+        .record_win(&game, &stats);
+    announce(&mut achievements, unlocked, &mut writer);
+}
+
+fn save_achievements(achievements: Res<Achievements>) {
+    if achievements.is_changed() && !achievements.is_added() {
+        storage::save(ACHIEVEMENTS_FILE, &*achievements);
+    }
+}
+
+#[derive(Component)]
+struct ToastArea;
+
+#[derive(Component)]
+struct Toast(Timer);
+
+fn setup_toast_area(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(20.0),
+            right: Val::Px(20.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(10.0),
+            ..default()
+        },
+        GlobalZIndex(3000),
+        ToastArea,
+    ));
+}
+
+fn spawn_toasts(
+    mut commands: Commands,
+    mut unlocked: MessageReader<AchievementUnlocked>,
+    game_assets: Res<GameAssets>,
+    area: Query<Entity, With<ToastArea>>,
+) {
+    let Ok(area) = area.single() else {
+        return;
+    };
+    for AchievementUnlocked(id) in unlocked.read() {
+        commands.entity(area).with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::all(Val::Px(15.0)),
+                        border: UiRect::left(Val::Px(6.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+                    BorderColor::all(Color::srgb(222. / 255., 154. / 255., 40. / 255.)),
+                    Toast(Timer::from_seconds(TOAST_SECS, TimerMode::Once)),
+                ))
+                .with_children(|toast| {
+                    toast.spawn((
+                        Text::new(format!("Achievement unlocked: {}", id.title())),
+                        TextFont {
+                            font: game_assets.font.clone(),
+                            font_size: 22.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(222. / 255., 154. / 255., 40. / 255.)),
+                    ));
+                    toast.spawn((
+                        Text::new(id.description()),
+                        TextFont {
+                            font: game_assets.font.clone(),
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+    }
+}
+
+fn expire_toasts(mut commands: Commands, time: Res<Time>, mut toasts: Query<(Entity, &mut Toast)>) {
+    for (entity, mut toast) in toasts.iter_mut() {
+        if toast.0.tick(time.delta()).is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[derive(Component)]
+struct AchievementsEntity;
+
+#[derive(Component)]
+struct AchievementsBackButton;
+
+fn setup_achievements_screen(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    achievements: Res<Achievements>,
+) {
+    let font = game_assets.font.clone();
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(game_assets.background),
+            ThemeBackground,
+            AchievementsEntity,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Achievements"),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 60.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            for id in AchievementId::all() {
+                let unlocked_on = achievements.unlocked_on(id);
+                let (accent, text_color) = if unlocked_on.is_some() {
+                    (
+                        Color::srgb(222. / 255., 154. / 255., 40. / 255.),
+                        Color::WHITE,
+                    )
+                } else {
+                    (
+                        Color::srgba(0.0, 0.0, 0.0, 0.4),
+                        Color::srgba(1.0, 1.0, 1.0, 0.5),
+                    )
+                };
+                parent
+                    .spawn((
+                        Node {
+                            width: Val::Px(700.0),
+                            padding: UiRect::axes(Val::Px(15.0), Val::Px(8.0)),
+                            column_gap: Val::Px(15.0),
+                            align_items: AlignItems::Center,
+                            border: UiRect::left(Val::Px(8.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.3)),
+                        BorderColor::all(accent),
+                    ))
+                    .with_children(|row| {
+                        row.spawn(Node {
+                            flex_direction: FlexDirection::Column,
+                            flex_grow: 1.0,
+                            ..default()
+                        })
+                        .with_children(|col| {
+                            col.spawn((
+                                Text::new(id.title()),
+                                TextFont {
+                                    font: font.clone(),
+                                    font_size: 24.0,
+                                    ..default()
+                                },
+                                TextColor(text_color),
+                            ));
+                            col.spawn((
+                                Text::new(id.description()),
+                                TextFont {
+                                    font: font.clone(),
+                                    font_size: 16.0,
+                                    ..default()
+                                },
+                                TextColor(text_color),
+                            ));
+                        });
+                        row.spawn((
+                            Text::new(unlocked_on.map_or("Locked".to_string(), |date| {
+                                date.format("%Y-%m-%d").to_string()
+                            })),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: 18.0,
+                                ..default()
+                            },
+                            TextColor(text_color),
+                        ));
+                    });
+            }
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(300.0),
+                        height: Val::Px(60.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.4, 0.4, 0.4)),
+                    AchievementsBackButton,
+                ))
+                .with_children(|p| {
+                    p.spawn((
+                        Text::new("Back"),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 25.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+}
+
+fn handle_achievements_interaction(
+    mut next_state: ResMut<NextState<GameState>>,
+    query: Query<&Interaction, (Changed<Interaction>, With<AchievementsBackButton>)>,
+) {
+    for interaction in query.iter() {
+        if *interaction == Interaction::Pressed {
+            next_state.set(GameState::Menu);
+        }
+    }
+}
+
+fn cleanup_achievements_screen(
+    mut commands: Commands,
+    query: Query<Entity, With<AchievementsEntity>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Rank, Suit};
+    use crate::systems::check_completed_sequences;
+
+    #[test]
+    fn lifting_a_suit_off_the_last_hidden_card_uncovers_the_column() {
+        let mut game = SpiderGame::default();
+        game.tableau[0] = vec![Card::new(Suit::Hearts, Rank::Five)];
+        game.tableau[0].extend(Rank::all().into_iter().rev().map(|rank| Card {
+            face_up: true,
+            ..Card::new(Suit::Spades, rank)
+        }));
+        let hidden = hidden_counts(&game);
+
+        assert_eq!(check_completed_sequences(&mut game).len(), 1);
+        let mut achievements = Achievements::default();
+        assert_eq!(
+            achievements.record_reveals(&hidden, &game),
+            vec![AchievementId::ColumnUncovered]
+        );
+        assert!(achievements.record_reveals(&hidden, &game).is_empty());
+    }
+}
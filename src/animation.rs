@@ -0,0 +1,165 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::resources::{AnimationEventQueue, GameState};
+use crate::settings::Settings;
+use crate::ui::MovingCard;
+
+pub const FLIGHT_SPEED: f32 = 3.5;
+pub const SETTLE_SPEED: f32 = 8.0;
+pub const DISPATCH_INTERVAL: f32 = 0.06;
+pub const CASCADE_DELAY: f32 = 0.15;
+
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AnimationPace>().add_systems(
+            Update,
+            (fast_forward_animations, update_animation_pace).chain(),
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AnimationSpeed {
+    Instant,
+    Fast,
+    #[default]
+    Normal,
+    Slow,
+}
+
+impl AnimationSpeed {
+    pub fn all() -> [AnimationSpeed; 4] {
+        [
+            AnimationSpeed::Instant,
+            AnimationSpeed::Fast,
+            AnimationSpeed::Normal,
+            AnimationSpeed::Slow,
+        ]
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            AnimationSpeed::Instant => "Instant",
+            AnimationSpeed::Fast => "Fast",
+            AnimationSpeed::Normal => "Normal",
+            AnimationSpeed::Slow => "Slow",
+        }
+    }
+
+    pub fn next(&self) -> AnimationSpeed {
+        let all = Self::all();
+        let index = all.iter().position(|s| s == self).unwrap();
+        all[(index + 1) % all.len()]
+    }
+
+    fn multiplier(&self) -> f32 {
+        match self {
+            AnimationSpeed::Instant => 1.0,
+            AnimationSpeed::Fast => 2.0,
+            AnimationSpeed::Normal => 1.0,
+            AnimationSpeed::Slow => 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Easing {
+    Linear,
+    #[default]
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    pub fn all() -> [Easing; 3] {
+        [Easing::Linear, Easing::EaseOut, Easing::EaseInOut]
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            Easing::Linear => "Linear",
+            Easing::EaseOut => "Ease Out",
+            Easing::EaseInOut => "Ease In-Out",
+        }
+    }
+
+    pub fn next(&self) -> Easing {
+        let all = Self::all();
+        let index = all.iter().position(|e| e == self).unwrap();
+        all[(index + 1) % all.len()]
+    }
+
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct AnimationPace {
+    pub speed: AnimationSpeed,
+    pub easing: Easing,
+    pub skipping: bool,
+}
+
+impl AnimationPace {
+    pub fn instant(&self) -> bool {
+        self.skipping || self.speed == AnimationSpeed::Instant
+    }
+
+    pub fn rate(&self, base: f32) -> f32 {
+        base * self.speed.multiplier()
+    }
+
+    pub fn duration(&self, base: f32) -> f32 {
+        if self.instant() {
+            0.0
+        } else {
+            base / self.speed.multiplier()
+        }
+    }
+
+    pub fn approach(&self, base_speed: f32, dt: f32) -> f32 {
+        if self.instant() {
+            1.0
+        } else {
+            (self.rate(base_speed) * dt).min(1.0)
+        }
+    }
+}
+
+pub fn fast_forward_animations(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<GameState>>,
+    queue_res: Res<AnimationEventQueue>,
+    moving_cards: Query<(), With<MovingCard>>,
+    mut pace: ResMut<AnimationPace>,
+) {
+    let animating = !queue_res.queue.is_empty() || !moving_cards.is_empty();
+    if !animating {
+        if pace.skipping {
+            pace.skipping = false;
+        }
+        return;
+    }
+    let pressed =
+        mouse_button.just_pressed(MouseButton::Left) || keyboard.just_pressed(KeyCode::Space);
+    if pressed && *state.get() == GameState::Playing {
+        pace.skipping = true;
+    }
+}
+
+fn update_animation_pace(settings: Res<Settings>, mut pace: ResMut<AnimationPace>) {
+    if pace.speed != settings.animation_speed || pace.easing != settings.easing {
+        pace.speed = settings.animation_speed;
+        pace.easing = settings.easing;
+    }
+}
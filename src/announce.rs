@@ -0,0 +1,333 @@
+use accesskit::{Live, Node as AccessNode, Role};
+use bevy::a11y::AccessibilityNode;
+use bevy::prelude::*;
+
+use crate::achievements::AchievementUnlocked;
+use crate::models::Card;
+use crate::resources::{AnimationEventQueue, GameAssets, GameState, PileType, SpiderGame};
+use crate::settings::Settings;
+use crate::systems::{
+    deal_stock_system, move_cards_system, undo_system, DealEvent, DealStockEvent, InvalidMoveEvent,
+    MoveAppliedEvent, SuitCompletedEvent, UndoEvent,
+};
+use crate::ui::MovingCard;
+
+const LOG_LINES: usize = 8;
+
+pub struct AnnouncePlugin;
+
+impl Plugin for AnnouncePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<Announcement>()
+            .add_systems(Startup, setup_log_panel)
+            .add_systems(
+                Update,
+                (
+                    announce_game_events
+                        .after(deal_stock_system)
+                        .after(move_cards_system)
+                        .after(undo_system),
+                    announce_board.run_if(in_state(GameState::Playing)),
+                    update_board_description,
+                    show_announcements,
+                    update_log_visibility,
+                )
+                    .chain(),
+            )
+            .add_systems(OnEnter(GameState::Won), announce_win)
+            .add_systems(OnEnter(GameState::Lost), announce_loss);
+    }
+}
+
+#[derive(Message)]
+pub struct Announcement(pub String);
+
+#[derive(Component)]
+struct LogPanel;
+
+#[derive(Component)]
+struct BoardDescription;
+
+fn pile_name(pile: PileType) -> String {
+    match pile {
+        PileType::Tableau(col) => format!("column {}", col + 1),
+        PileType::Stock => "the stock".to_string(),
+        PileType::Foundation(_) => "the foundation".to_string(),
+    }
+}
+
+fn describe_move(ev: &MoveAppliedEvent) -> String {
+    let cards = match ev.count {
+        1 => ev.card.name(),
+        2 => format!("{} and 1 card", ev.card.name()),
+        count => format!("{} and {} cards", ev.card.name(), count - 1),
+    };
+    format!(
+        "Moved {} from {} to {}",
+        cards,
+        pile_name(ev.from),
+        pile_name(ev.to)
+    )
+}
+
+fn describe_column(index: usize, column: &[Card]) -> String {
+    let hidden = column.iter().filter(|c| !c.face_up).count();
+    let visible: Vec<String> = column
+        .iter()
+        .filter(|c| c.face_up)
+        .map(Card::name)
+        .collect();
+    let contents = match (hidden, visible.is_empty()) {
+        (0, true) => "empty".to_string(),
+        (0, false) => visible.join(", "),
+        (_, true) => format!("{} hidden", hidden),
+        (_, false) => format!("{} hidden, then {}", hidden, visible.join(", ")),
+    };
+    format!("Column {}: {}", index + 1, contents)
+}
+
+fn describe_board(game: &SpiderGame) -> Vec<String> {
+    let mut lines: Vec<String> = game
+        .tableau
+        .iter()
+        .enumerate()
+        .map(|(i, column)| describe_column(i, column))
+        .collect();
+    lines.push(format!(
+        "Stock: {} deals left",
+        game.stock.len().div_ceil(10)
+    ));
+    lines.push(format!("Suits completed: {}/8", game.foundations.len()));
+    lines
+}
+
+fn setup_log_panel(mut commands: Commands) {
+    let mut log = AccessNode::new(Role::Log);
+    log.set_label("Game log");
+    log.set_live(Live::Polite);
+    let mut board = AccessNode::new(Role::Paragraph);
+    board.set_label("Board");
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(70.0),
+                left: Val::Px(20.0),
+                max_width: Val::Px(420.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            GlobalZIndex(1000),
+            Visibility::Hidden,
+            AccessibilityNode(log),
+            LogPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    display: Display::None,
+                    ..default()
+                },
+                AccessibilityNode(board),
+                BoardDescription,
+            ));
+        });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn announce_game_events(
+    game: Res<SpiderGame>,
+    mut deals: MessageReader<DealEvent>,
+    mut stock_deals: MessageReader<DealStockEvent>,
+    mut moves: MessageReader<MoveAppliedEvent>,
+    mut invalid_moves: MessageReader<InvalidMoveEvent>,
+    mut suits: MessageReader<SuitCompletedEvent>,
+    mut undos: MessageReader<UndoEvent>,
+    mut unlocked: MessageReader<AchievementUnlocked>,
+    mut last_undo_count: Local<u32>,
+    mut writer: MessageWriter<Announcement>,
+) {
+    for DealEvent(difficulty, _) in deals.read() {
+        writer.write(Announcement(format!(
+            "New {} game. Press B to hear the board.",
+            difficulty.label()
+        )));
+    }
+    for _ in stock_deals.read() {
+        writer.write(Announcement(format!(
+            "Dealt a row from the stock, {} deals left",
+            game.stock.len().div_ceil(10)
+        )));
+    }
+    for ev in moves.read() {
+        let mut text = describe_move(ev);
+        if let PileType::Tableau(col) = ev.from {
+            match game.tableau[col].last() {
+                Some(card) if ev.revealed => text += &format!(", revealing {}", card.name()),
+                None => text += &format!(", column {} is now empty", col + 1),
+                _ => {}
+            }
+        }
+        writer.write(Announcement(text));
+    }
+    for ev in invalid_moves.read() {
+        let text = match ev.to {
+            Some(to) => format!(
+                "Invalid move: {} can't go to {}",
+                ev.card.name(),
+                pile_name(to)
+            ),
+            None => format!(
+                "Invalid move: the cards on {} are not a run",
+                ev.card.name()
+            ),
+        };
+        writer.write(Announcement(text));
+    }
+    for ev in suits.read() {
+        writer.write(Announcement(format!(
+            "Completed a run of {}, {}/8 suits done",
+            ev.suit.name(),
+            ev.foundation + 1
+        )));
+    }
+    if undos.read().count() > 0 && game.undo_count > *last_undo_count {
+        writer.write(Announcement("Move undone".to_string()));
+    }
+    *last_undo_count = game.undo_count;
+    for AchievementUnlocked(id) in unlocked.read() {
+        writer.write(Announcement(format!(
+            "Achievement unlocked: {}",
+            id.title()
+        )));
+    }
+}
+
+fn announce_board(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    game: Res<SpiderGame>,
+    mut writer: MessageWriter<Announcement>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyB) {
+        for line in describe_board(&game) {
+            writer.write(Announcement(line));
+        }
+    }
+}
+
+fn announce_win(mut writer: MessageWriter<Announcement>) {
+    writer.write(Announcement("All suits completed, you win!".to_string()));
+}
+
+fn announce_loss(mut writer: MessageWriter<Announcement>) {
+    writer.write(Announcement("Out of points, game over".to_string()));
+}
+
+fn update_board_description(
+    game: Res<SpiderGame>,
+    queue_res: Res<AnimationEventQueue>,
+    moving_cards: Query<(), With<MovingCard>>,
+    mut description: Query<&mut AccessibilityNode, With<BoardDescription>>,
+    mut pending: Local<bool>,
+) {
+    if !queue_res.queue.is_empty() || !moving_cards.is_empty() {
+        *pending = true;
+        return;
+    }
+    if !game.is_changed() && !*pending {
+        return;
+    }
+    *pending = false;
+    if let Ok(mut node) = description.single_mut() {
+        node.set_description(describe_board(&game).join(". "));
+    }
+}
+
+fn show_announcements(
+    mut commands: Commands,
+    mut announcements: MessageReader<Announcement>,
+    game_assets: Res<GameAssets>,
+    panel: Query<(Entity, &Children), With<LogPanel>>,
+    lines: Query<(), With<Text>>,
+) {
+    let Ok((panel, children)) = panel.single() else {
+        return;
+    };
+    let mut shown: Vec<Entity> = children.iter().filter(|e| lines.contains(*e)).collect();
+    for Announcement(text) in announcements.read() {
+        debug!("{}", text);
+        let line = commands
+            .spawn((
+                Text::new(text.clone()),
+                TextFont {
+                    font: game_assets.font.clone(),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Label,
+            ))
+            .id();
+        commands.entity(panel).add_child(line);
+        shown.push(line);
+    }
+    if shown.len() > LOG_LINES {
+        for entity in shown.drain(..shown.len() - LOG_LINES) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn update_log_visibility(
+    settings: Res<Settings>,
+    state: Res<State<GameState>>,
+    mut panel: Query<&mut Visibility, With<LogPanel>>,
+) {
+    if !settings.is_changed() && !state.is_changed() {
+        return;
+    }
+    let in_game = matches!(
+        state.get(),
+        GameState::Playing | GameState::Won | GameState::Lost
+    );
+    if let Ok(mut visibility) = panel.single_mut() {
+        *visibility = if settings.move_log && in_game {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Rank, Suit};
+
+    fn moved(count: usize) -> String {
+        describe_move(&MoveAppliedEvent {
+            from: PileType::Tableau(2),
+            to: PileType::Tableau(6),
+            card: Card::new(Suit::Spades, Rank::Nine),
+            count,
+            revealed: false,
+        })
+    }
+
+    #[test]
+    fn moves_name_the_lead_card_and_the_columns() {
+        assert_eq!(moved(1), "Moved 9 of spades from column 3 to column 7");
+        assert_eq!(
+            moved(2),
+            "Moved 9 of spades and 1 card from column 3 to column 7"
+        );
+        assert_eq!(
+            moved(4),
+            "Moved 9 of spades and 3 cards from column 3 to column 7"
+        );
+    }
+}
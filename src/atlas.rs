@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use bevy::image::TextureAtlasBuilder;
+use bevy::prelude::*;
+
+use crate::card_render::apply_procedural_cards;
+use crate::layout::{CARD_HEIGHT, CARD_WIDTH};
+use crate::models::{Card, Rank, Suit};
+use crate::resources::GameAssets;
+
+const ATLAS_PADDING: u32 = 2;
+const ATLAS_MAX_SIZE: u32 = 8192;
+
+pub struct AtlasPlugin;
+
+impl Plugin for AtlasPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CardAtlas>()
+            .add_systems(Update, build_card_atlas.after(apply_procedural_cards));
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct CardAtlas {
+    image: Handle<Image>,
+    layout: Handle<TextureAtlasLayout>,
+    faces: HashMap<(Suit, Rank), usize>,
+    back: usize,
+    sources: Vec<AssetId<Image>>,
+    waiting: bool,
+    ready: bool,
+}
+
+impl CardAtlas {
+    pub fn card_sprite(&self, card: &Card, game_assets: &GameAssets) -> Sprite {
+        let index = if card.face_up {
+            self.faces.get(&(card.suit, card.rank)).copied()
+        } else {
+            Some(self.back)
+        };
+        let mut sprite = match index {
+            Some(index) if self.ready => Sprite::from_atlas_image(
+                self.image.clone(),
+                TextureAtlas {
+                    layout: self.layout.clone(),
+                    index,
+                },
+            ),
+            _ => Sprite::from_image(game_assets.card_texture(card)),
+        };
+        sprite.custom_size = Some(Vec2::new(CARD_WIDTH, CARD_HEIGHT));
+        sprite
+    }
+}
+
+fn atlas_sources(game_assets: &GameAssets) -> Vec<((Suit, Rank), Handle<Image>)> {
+    let mut sources: Vec<((Suit, Rank), Handle<Image>)> = Suit::all()
+        .into_iter()
+        .flat_map(|suit| Rank::all().map(|rank| (suit, rank)))
+        .filter_map(|key| game_assets.faces.get(&key).map(|h| (key, h.clone())))
+        .collect();
+    sources.push(((Suit::Spades, Rank::Ace), game_assets.back_texture.clone()));
+    sources
+}
+
+fn build_card_atlas(
+    game_assets: Res<GameAssets>,
+    mut atlas: ResMut<CardAtlas>,
+    mut images: ResMut<Assets<Image>>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    if game_assets.faces.is_empty() || (!game_assets.is_changed() && !atlas.waiting) {
+        return;
+    }
+    let sources = atlas_sources(&game_assets);
+    let ids: Vec<AssetId<Image>> = sources.iter().map(|(_, h)| h.id()).collect();
+    if atlas.sources == ids && !atlas.waiting {
+        return;
+    }
+    if !atlas.waiting {
+        atlas.ready = false;
+        atlas.waiting = true;
+    }
+    if ids.iter().any(|id| images.get(*id).is_none()) {
+        return;
+    }
+
+    let mut builder = TextureAtlasBuilder::default();
+    builder
+        .padding(UVec2::splat(ATLAS_PADDING))
+        .max_size(UVec2::splat(ATLAS_MAX_SIZE));
+    for id in &ids {
+        builder.add_texture(Some(*id), images.get(*id).unwrap());
+    }
+    let (layout, _, mut image) = match builder.build() {
+        Ok(built) => built,
+        Err(err) => {
+            warn!("Failed to build card atlas: {}", err);
+            atlas.sources = ids;
+            atlas.waiting = false;
+            return;
+        }
+    };
+    image.sampler = images
+        .get(game_assets.back_texture.id())
+        .unwrap()
+        .sampler
+        .clone();
+
+    let back = sources.len() - 1;
+    let faces = sources[..back]
+        .iter()
+        .enumerate()
+        .map(|(index, (key, _))| (*key, index))
+        .collect();
+
+    *atlas = CardAtlas {
+        image: images.add(image),
+        layout: layouts.add(layout),
+        faces,
+        back,
+        sources: ids,
+        waiting: false,
+        ready: true,
+    };
+}
@@ -0,0 +1,472 @@
+use std::io;
+use std::process;
+use std::time::{Duration, Instant};
+
+use chrono::NaiveDate;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use learn_rust::achievements::{hidden_counts, AchievementId, Achievements, ACHIEVEMENTS_FILE};
+use learn_rust::daily::{daily_date, DailyResults, DAILY_RESULTS_FILE};
+use learn_rust::models::{Card, Suit};
+use learn_rust::resources::{Difficulty, GameMode, SpiderGame};
+use learn_rust::settings::{Settings, SETTINGS_FILE};
+use learn_rust::stats::{Statistics, STATS_FILE};
+use learn_rust::storage;
+use learn_rust::systems::{check_completed_sequences, deal_seed};
+use learn_rust::ui::format_time;
+use learn_rust::winnable::{WinnableSeeds, WINNABLE_SEEDS_FILE};
+
+const TICK: Duration = Duration::from_millis(250);
+const COLUMN_WIDTH: usize = 5;
+const USAGE: &str = "usage: spider-tui [--suits 1|2|4] [--seed N | --daily [YYYY-MM-DD]]";
+const HELP: &str = "37 move from column 3 to 7 · 374 move 4 cards · 0 is column 10 · d deal · u undo · n [1|2|4] new game · r replay · q quit";
+
+struct Tui {
+    game: SpiderGame,
+    history: Vec<SpiderGame>,
+    settings: Settings,
+    stats: Statistics,
+    achievements: Achievements,
+    daily: DailyResults,
+    input: String,
+    message: String,
+    error: bool,
+    recorded: bool,
+    clock: Instant,
+}
+
+impl Tui {
+    fn new(difficulty: Difficulty, mode: GameMode) -> Self {
+        let mut tui = Tui {
+            game: SpiderGame::default(),
+            history: Vec::new(),
+            settings: storage::load(SETTINGS_FILE),
+            stats: storage::load(STATS_FILE),
+            achievements: storage::load(ACHIEVEMENTS_FILE),
+            daily: storage::load(DAILY_RESULTS_FILE),
+            input: String::new(),
+            message: String::new(),
+            error: false,
+            recorded: true,
+            clock: Instant::now(),
+        };
+        tui.deal(difficulty, mode);
+        tui
+    }
+
+    fn deal(&mut self, difficulty: Difficulty, mode: GameMode) {
+        self.finish();
+        let seed = if mode == GameMode::Classic && self.settings.winnable_deals {
+            let mut winnable_seeds: WinnableSeeds = storage::load(WINNABLE_SEEDS_FILE);
+            let seed = deal_seed(mode, difficulty, &self.settings, &mut winnable_seeds);
+            storage::save(WINNABLE_SEEDS_FILE, &winnable_seeds);
+            seed
+        } else {
+            deal_seed(
+                mode,
+                difficulty,
+                &self.settings,
+                &mut WinnableSeeds::default(),
+            )
+        };
+        self.game = SpiderGame {
+            difficulty,
+            mode,
+            seed,
+            scoring: self.settings.scoring,
+            score: self
+                .settings
+                .scoring
+                .starting_score(self.stats.vegas_balance),
+            ..SpiderGame::default()
+        };
+        self.game.deal_tableau();
+        self.history.clear();
+        self.recorded = false;
+        self.clock = Instant::now();
+        self.info(format!("Dealt {} seed {}", difficulty.label(), seed));
+    }
+
+    fn finish(&mut self) {
+        if self.recorded {
+            return;
+        }
+        self.recorded = true;
+        self.stats.record(&self.game);
+        self.stats.sync_vegas_balance(&self.game);
+        storage::save(STATS_FILE, &self.stats);
+        if self.game.is_won() {
+            let unlocked = self.achievements.record_win(&self.game, &self.stats);
+            self.save_achievements(unlocked);
+        }
+        if self.daily.record_game(&self.game) {
+            storage::save(DAILY_RESULTS_FILE, &self.daily);
+        }
+    }
+
+    fn save_achievements(&self, unlocked: Vec<AchievementId>) {
+        if !unlocked.is_empty() {
+            storage::save(ACHIEVEMENTS_FILE, &self.achievements);
+        }
+    }
+
+    fn tick(&mut self) {
+        let now = Instant::now();
+        if !self.recorded {
+            self.game.elapsed_secs += (now - self.clock).as_secs_f32();
+        }
+        self.clock = now;
+    }
+
+    fn info(&mut self, message: String) {
+        self.message = message;
+        self.error = false;
+    }
+
+    fn fail(&mut self, message: String) {
+        self.message = message;
+        self.error = true;
+    }
+
+    fn run(&mut self, command: &str) -> bool {
+        let command = command.trim().to_lowercase();
+        let mut words = command.split_whitespace();
+        match (words.next(), words.next()) {
+            (None, _) => {}
+            (Some("q"), None) => return true,
+            (Some("n"), None) => self.deal(self.game.difficulty, GameMode::Classic),
+            (Some("n"), Some(suits)) => match parse_suits(suits) {
+                Some(difficulty) => self.deal(difficulty, GameMode::Classic),
+                None => self.fail(format!("No {}-suit game, pick 1, 2 or 4", suits)),
+            },
+            (Some("r"), None) => {
+                let mode = match self.game.mode {
+                    GameMode::Daily(date) => GameMode::Daily(date),
+                    _ => GameMode::Seeded(self.game.seed),
+                };
+                self.deal(self.game.difficulty, mode);
+            }
+            _ if self.recorded => self.fail("The game is over, n for a new game".to_string()),
+            (Some("d"), None) => self.deal_row(),
+            (Some("u"), None) => self.undo(),
+            _ => match parse_move(&command) {
+                Some((from, to, count)) => self.move_run(from, to, count),
+                None => self.fail(format!("Unknown command \"{}\"", command)),
+            },
+        }
+        false
+    }
+
+    fn move_run(&mut self, from: usize, to: usize, count: Option<usize>) {
+        let len = self.game.tableau[from].len();
+        let index = match count {
+            Some(count) => len
+                .checked_sub(count)
+                .filter(|&index| self.game.can_move(from, index, to)),
+            None => (0..len).find(|&index| self.game.can_move(from, index, to)),
+        };
+        let Some(index) = index else {
+            return self.fail(if len == 0 {
+                format!("Column {} is empty", from + 1)
+            } else {
+                format!("Nothing in column {} fits on column {}", from + 1, to + 1)
+            });
+        };
+        self.history.push(self.game.clone());
+        let moved = self.game.move_cards(from, index, to).unwrap();
+        let cards: Vec<String> = moved.cards.iter().map(card_text).collect();
+        self.info(format!(
+            "Moved {} from {} to {}",
+            cards.join(" "),
+            from + 1,
+            to + 1
+        ));
+        self.after_turn();
+    }
+
+    fn deal_row(&mut self) {
+        if self.game.stock.is_empty() {
+            return self.fail("The stock is empty".to_string());
+        }
+        self.history.push(self.game.clone());
+        self.game.deal_row();
+        self.info("Dealt a row".to_string());
+        self.after_turn();
+    }
+
+    fn undo(&mut self) {
+        if !self.game.can_undo() {
+            return self.fail("No undos left this game".to_string());
+        }
+        match self.history.pop() {
+            Some(previous) => {
+                self.game.undo_to(previous);
+                self.info("Undone".to_string());
+            }
+            None => self.fail("Nothing to undo".to_string()),
+        }
+    }
+
+    fn after_turn(&mut self) {
+        for run in check_completed_sequences(&mut self.game) {
+            let unlocked = self.achievements.record_suit(self.game.stock.len());
+            self.save_achievements(unlocked);
+            self.info(format!(
+                "Completed {} from column {}",
+                suit_symbol(run.cards[0].suit),
+                run.column + 1
+            ));
+        }
+        if let Some(hidden) = self.history.last().map(hidden_counts) {
+            let unlocked = self.achievements.record_reveals(&hidden, &self.game);
+            self.save_achievements(unlocked);
+        }
+        if self.game.is_won() {
+            self.finish();
+            self.info(format!(
+                "You won with {} in {} moves! n for a new game, r to replay, q to quit",
+                self.game.scoring.format_score(self.game.score),
+                self.game.move_count
+            ));
+        } else if self.game.is_lost() {
+            self.finish();
+            self.fail("Out of points. n for a new game, r to replay, q to quit".to_string());
+        }
+    }
+
+    fn suit_style(&self, suit: Suit) -> Style {
+        let color = match suit {
+            Suit::Diamonds if self.settings.four_colour_deck => Color::Blue,
+            Suit::Clubs if self.settings.four_colour_deck => Color::Green,
+            _ if suit.is_red() && self.settings.high_contrast => Color::LightRed,
+            _ if suit.is_red() => Color::Red,
+            _ => Color::Reset,
+        };
+        let style = Style::default().fg(color);
+        if self.settings.high_contrast {
+            style.add_modifier(Modifier::BOLD)
+        } else {
+            style
+        }
+    }
+
+    fn card_span(&self, card: &Card) -> Span<'static> {
+        Span::styled(
+            format!("{:>3}", card_text(card)),
+            self.suit_style(card.suit),
+        )
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [status, piles, table, message, prompt, help] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let game = &self.game;
+        let mode = match game.mode {
+            GameMode::Classic => "Classic".to_string(),
+            GameMode::Daily(date) => format!("Daily {}", date),
+            GameMode::Seeded(_) => "Seeded".to_string(),
+        };
+        frame.render_widget(
+            Paragraph::new(format!(
+                "Spider · {} · {} · seed {} · {} · Score {} · Moves {} · Undos {} · Time {}",
+                game.difficulty.label(),
+                mode,
+                game.seed,
+                game.scoring.label(),
+                game.scoring.format_score(game.score),
+                game.move_count,
+                game.undo_count,
+                format_time(game.elapsed_secs)
+            )),
+            status,
+        );
+
+        let mut spans = vec![Span::raw(format!(
+            "Stock: {} deal(s) left   Foundations:",
+            game.stock.len() / 10
+        ))];
+        for slot in 0..8 {
+            spans.push(Span::raw(" "));
+            spans.push(match game.foundations.get(slot) {
+                Some(king) => self.card_span(king),
+                None => Span::styled("  ·", Style::default().fg(Color::DarkGray)),
+            });
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), piles);
+
+        let hidden = Style::default().fg(Color::DarkGray);
+        let columns: Vec<Vec<Span>> = game
+            .tableau
+            .iter()
+            .map(|pile| {
+                let face_down = pile.iter().take_while(|card| !card.face_up).count();
+                let mut cells = Vec::new();
+                if pile.is_empty() {
+                    cells.push(Span::styled("  ·", hidden));
+                } else if face_down > 0 {
+                    cells.push(Span::styled(format!(" ▒{}", face_down), hidden));
+                }
+                cells.extend(pile[face_down..].iter().map(|card| self.card_span(card)));
+                cells
+            })
+            .collect();
+        let header: String = (1..=10)
+            .map(|col| format!("{:>3}{}", col, " ".repeat(COLUMN_WIDTH - 3)))
+            .collect();
+        let mut lines = vec![Line::styled(
+            header,
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        let rows = columns.iter().map(Vec::len).max().unwrap_or(0);
+        for row in 0..rows {
+            let mut spans = Vec::new();
+            for cells in &columns {
+                spans.push(cells.get(row).cloned().unwrap_or(Span::raw("   ")));
+                spans.push(Span::raw(" ".repeat(COLUMN_WIDTH - 3)));
+            }
+            lines.push(Line::from(spans));
+        }
+        let block = Block::bordered();
+        let visible = block.inner(table).height as usize;
+        let scroll = lines.len().saturating_sub(visible) as u16;
+        frame.render_widget(
+            Paragraph::new(lines).block(block).scroll((scroll, 0)),
+            table,
+        );
+
+        let color = if self.error { Color::Red } else { Color::Green };
+        frame.render_widget(
+            Paragraph::new(self.message.as_str()).style(Style::default().fg(color)),
+            message,
+        );
+        frame.render_widget(Paragraph::new(format!("> {}", self.input)), prompt);
+        frame.set_cursor_position((prompt.x + 2 + self.input.chars().count() as u16, prompt.y));
+        frame.render_widget(
+            Paragraph::new(HELP).style(Style::default().fg(Color::DarkGray)),
+            help,
+        );
+    }
+}
+
+fn suit_symbol(suit: Suit) -> &'static str {
+    match suit {
+        Suit::Hearts => "♥",
+        Suit::Diamonds => "♦",
+        Suit::Clubs => "♣",
+        Suit::Spades => "♠",
+    }
+}
+
+fn card_text(card: &Card) -> String {
+    format!("{}{}", card.rank.as_str(), suit_symbol(card.suit))
+}
+
+fn parse_suits(suits: &str) -> Option<Difficulty> {
+    Difficulty::all()
+        .into_iter()
+        .find(|difficulty| suits == (*difficulty as u8).to_string())
+}
+
+fn parse_move(command: &str) -> Option<(usize, usize, Option<usize>)> {
+    let digits: Vec<usize> = command
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_digit(10).map(|d| d as usize))
+        .collect::<Option<_>>()?;
+    let column = |digit: usize| (digit + 9) % 10;
+    match digits.as_slice() {
+        [from, to] => Some((column(*from), column(*to), None)),
+        [from, to, count @ ..] if count.len() <= 3 => Some((
+            column(*from),
+            column(*to),
+            Some(count.iter().fold(0, |n, digit| n * 10 + digit)),
+        )),
+        _ => None,
+    }
+}
+
+fn parse_args() -> Result<(Difficulty, GameMode), String> {
+    let mut difficulty = Difficulty::Easy;
+    let mut mode = GameMode::Classic;
+    let mut args = std::env::args().skip(1).peekable();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--suits" => {
+                let suits = args.next().unwrap_or_default();
+                difficulty = parse_suits(&suits).ok_or(format!("no {}-suit game", suits))?;
+            }
+            "--seed" => {
+                let seed = args.next().unwrap_or_default();
+                mode = GameMode::Seeded(seed.parse().map_err(|_| format!("bad seed {}", seed))?);
+            }
+            "--daily" => {
+                let date = match args.next_if(|arg| !arg.starts_with("--")) {
+                    Some(date) => NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                        .map_err(|_| format!("bad date {}", date))?,
+                    None => daily_date(),
+                };
+                mode = GameMode::Daily(date);
+            }
+            _ => return Err(format!("unknown argument {}", arg)),
+        }
+    }
+    Ok((difficulty, mode))
+}
+
+fn play(terminal: &mut DefaultTerminal, tui: &mut Tui) -> io::Result<()> {
+    loop {
+        tui.tick();
+        terminal.draw(|frame| tui.draw(frame))?;
+        if !event::poll(TICK)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+            KeyCode::Char(c) => tui.input.push(c),
+            KeyCode::Backspace => {
+                tui.input.pop();
+            }
+            KeyCode::Esc => tui.input.clear(),
+            KeyCode::Enter => {
+                let command = std::mem::take(&mut tui.input);
+                if tui.run(&command) {
+                    return Ok(());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn main() -> io::Result<()> {
+    let (difficulty, mode) = parse_args().unwrap_or_else(|err| {
+        eprintln!("spider-tui: {}\n{}", err, USAGE);
+        process::exit(2);
+    });
+    let mut tui = Tui::new(difficulty, mode);
+    let mut terminal = ratatui::init();
+    let result = play(&mut terminal, &mut tui);
+    ratatui::restore();
+    tui.tick();
+    tui.finish();
+    result
+}
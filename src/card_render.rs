@@ -0,0 +1,711 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::asset::{AssetLoadFailedEvent, RenderAssetUsages};
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::tasks::futures::check_ready;
+use bevy::tasks::{AsyncComputeTaskPool, Task};
+use bevy::window::PrimaryWindow;
+
+use crate::layout::{CARD_HEIGHT, CARD_WIDTH};
+use crate::models::{Rank, Suit};
+use crate::resources::GameAssets;
+use crate::settings::Settings;
+use crate::theme::apply_theme;
+use crate::ui::CardTarget;
+
+const MIN_RESOLUTION: f32 = 3.0;
+const MAX_RESOLUTION: f32 = 6.0;
+const SUPERSAMPLE: u32 = 4;
+const CORNER_RADIUS: f32 = 8.0;
+const BORDER: f32 = 1.5;
+const INDEX_CELL: f32 = 2.0;
+const CONTRAST_INDEX_CELL: f32 = 2.5;
+const COURT_CELL: f32 = 6.0;
+const PIP_RADIUS: f32 = 10.0;
+const INDEX_PIP_RADIUS: f32 = 5.5;
+const CONTRAST_INDEX_PIP_RADIUS: f32 = 7.0;
+const PIP_COLUMNS: [f32; 3] = [38.0, 60.0, 82.0];
+const PIP_TOP: f32 = 32.0;
+const PIP_BOTTOM: f32 = 136.0;
+
+const WHITE: [u8; 3] = [250, 250, 245];
+const EDGE: [u8; 3] = [60, 60, 60];
+const RED: [u8; 3] = [200, 30, 45];
+const BLACK: [u8; 3] = [25, 25, 30];
+const BACK: [u8; 3] = [35, 55, 115];
+const BACK_LINE: [u8; 3] = [80, 105, 170];
+const FOUR_COLOUR_BLUE: [u8; 3] = [20, 80, 200];
+const FOUR_COLOUR_GREEN: [u8; 3] = [15, 125, 45];
+const CONTRAST_WHITE: [u8; 3] = [255, 255, 255];
+const CONTRAST_RED: [u8; 3] = [210, 0, 0];
+const CONTRAST_BLACK: [u8; 3] = [0, 0, 0];
+const PATTERN_FACE: [u8; 3] = [150, 150, 150];
+const PATTERN_BACK: [u8; 3] = [235, 235, 235];
+const SLOT_EDGE: [u8; 3] = [220, 225, 215];
+
+pub struct CardRenderPlugin;
+
+impl Plugin for CardRenderPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProceduralCards>().add_systems(
+            Update,
+            (
+                collect_failed_card_art,
+                render_procedural_cards,
+                apply_procedural_cards.after(apply_theme),
+            )
+                .chain(),
+        );
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Default)]
+struct CardStyle {
+    four_colour: bool,
+    high_contrast: bool,
+    patterns: bool,
+}
+
+impl CardStyle {
+    fn from_settings(settings: &Settings) -> Self {
+        Self {
+            four_colour: settings.four_colour_deck,
+            high_contrast: settings.high_contrast,
+            patterns: settings.card_patterns,
+        }
+    }
+
+    fn suit_color(&self, suit: Suit) -> [u8; 3] {
+        match suit {
+            Suit::Diamonds if self.four_colour => FOUR_COLOUR_BLUE,
+            Suit::Clubs if self.four_colour => FOUR_COLOUR_GREEN,
+            _ if suit.is_red() && self.high_contrast => CONTRAST_RED,
+            _ if suit.is_red() => RED,
+            _ if self.high_contrast => CONTRAST_BLACK,
+            _ => BLACK,
+        }
+    }
+
+    fn face_color(&self) -> [u8; 3] {
+        if self.high_contrast {
+            CONTRAST_WHITE
+        } else {
+            WHITE
+        }
+    }
+
+    fn edge_color(&self) -> [u8; 3] {
+        if self.high_contrast {
+            CONTRAST_BLACK
+        } else {
+            EDGE
+        }
+    }
+
+    fn index_size(&self) -> (f32, f32) {
+        if self.high_contrast {
+            (CONTRAST_INDEX_CELL, CONTRAST_INDEX_PIP_RADIUS)
+        } else {
+            (INDEX_CELL, INDEX_PIP_RADIUS)
+        }
+    }
+}
+
+struct RenderedCards {
+    style: CardStyle,
+    resolution: f32,
+    faces: Vec<((Suit, Rank), Image)>,
+    back: Image,
+    slot: Image,
+}
+
+#[derive(Resource, Default)]
+pub struct ProceduralCards {
+    faces: HashMap<(Suit, Rank), Handle<Image>>,
+    back: Handle<Image>,
+    slot: Handle<Image>,
+    style: CardStyle,
+    resolution: f32,
+    failed: HashSet<AssetId<Image>>,
+    superseded: HashSet<AssetId<Image>>,
+    task: Option<Task<RenderedCards>>,
+}
+
+fn collect_failed_card_art(
+    mut failed_events: MessageReader<AssetLoadFailedEvent<Image>>,
+    game_assets: Res<GameAssets>,
+    mut procedural: ResMut<ProceduralCards>,
+) {
+    for ev in failed_events.read() {
+        let is_card_art = game_assets.back_texture.id() == ev.id
+            || game_assets.blank_texture.id() == ev.id
+            || game_assets
+                .faces
+                .values()
+                .any(|handle| handle.id() == ev.id);
+        if is_card_art {
+            warn!("Card art {} failed to load, drawing it instead", ev.path);
+            procedural.failed.insert(ev.id);
+        }
+    }
+}
+
+fn render_procedural_cards(
+    settings: Res<Settings>,
+    mut procedural: ResMut<ProceduralCards>,
+    mut images: ResMut<Assets<Image>>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    targets: Query<&CardTarget>,
+) {
+    let style = CardStyle::from_settings(&settings);
+    let scale_factor = window.single().map_or(1.0, Window::scale_factor);
+    let card_scale = targets
+        .iter()
+        .map(|target| target.scale)
+        .fold(0.0, f32::max);
+    let resolution = (card_scale * scale_factor)
+        .ceil()
+        .clamp(MIN_RESOLUTION, MAX_RESOLUTION);
+    if let Some(task) = procedural.task.as_mut() {
+        let Some(rendered) = check_ready(task) else {
+            return;
+        };
+        procedural.task = None;
+        let previous: Vec<AssetId<Image>> = procedural
+            .faces
+            .values()
+            .chain([&procedural.back, &procedural.slot])
+            .map(|handle| handle.id())
+            .collect();
+        procedural.superseded.extend(previous);
+        for (key, image) in rendered.faces {
+            procedural.faces.insert(key, images.add(image));
+        }
+        procedural.back = images.add(rendered.back);
+        procedural.slot = images.add(rendered.slot);
+        procedural.style = rendered.style;
+        procedural.resolution = rendered.resolution;
+    } else if (procedural.faces.is_empty()
+        || procedural.style != style
+        || resolution > procedural.resolution)
+        && (settings.drawn_cards() || !procedural.failed.is_empty())
+    {
+        procedural.task = Some(AsyncComputeTaskPool::get().spawn(async move {
+            let faces = Suit::all()
+                .into_iter()
+                .flat_map(|suit| Rank::all().map(|rank| (suit, rank)))
+                .map(|(suit, rank)| ((suit, rank), render_face(suit, rank, style, resolution)))
+                .collect();
+            RenderedCards {
+                style,
+                resolution,
+                faces,
+                back: render_back(style, resolution),
+                slot: render_slot(resolution),
+            }
+        }));
+    }
+}
+
+pub fn apply_procedural_cards(
+    settings: Res<Settings>,
+    mut procedural: ResMut<ProceduralCards>,
+    mut game_assets: ResMut<GameAssets>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    if procedural.faces.is_empty() {
+        return;
+    }
+    let replace = |handle: &Handle<Image>| {
+        settings.drawn_cards()
+            || procedural.failed.contains(&handle.id())
+            || procedural.superseded.contains(&handle.id())
+    };
+    let stale_faces: Vec<(Suit, Rank)> = game_assets
+        .faces
+        .iter()
+        .filter(|(key, handle)| replace(handle) && procedural.faces[*key] != **handle)
+        .map(|(key, _)| *key)
+        .collect();
+    for key in stale_faces {
+        game_assets
+            .faces
+            .insert(key, procedural.faces[&key].clone());
+    }
+    if replace(&game_assets.back_texture) && game_assets.back_texture != procedural.back {
+        game_assets.back_texture = procedural.back.clone();
+    }
+    let stale_slot = procedural.failed.contains(&game_assets.blank_texture.id())
+        || procedural
+            .superseded
+            .contains(&game_assets.blank_texture.id());
+    if stale_slot && game_assets.blank_texture != procedural.slot {
+        game_assets.blank_texture = procedural.slot.clone();
+    }
+
+    if procedural.superseded.is_empty() {
+        return;
+    }
+    let in_use: HashSet<AssetId<Image>> = game_assets
+        .faces
+        .values()
+        .chain([&game_assets.back_texture, &game_assets.blank_texture])
+        .map(|handle| handle.id())
+        .collect();
+    for id in std::mem::take(&mut procedural.superseded) {
+        if in_use.contains(&id) {
+            procedural.superseded.insert(id);
+        } else {
+            images.remove(id);
+        }
+    }
+}
+
+struct Canvas {
+    width: u32,
+    height: u32,
+    resolution: f32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(resolution: f32) -> Self {
+        let width = (CARD_WIDTH * resolution) as u32;
+        let height = (CARD_HEIGHT * resolution) as u32;
+        Self {
+            width,
+            height,
+            resolution,
+            pixels: vec![0; (width * height * 4) as usize],
+        }
+    }
+
+    fn blend(&mut self, x: u32, y: u32, color: [u8; 3], coverage: f32) {
+        let i = ((y * self.width + x) * 4) as usize;
+        let pixel = &mut self.pixels[i..i + 4];
+        if coverage >= 1.0 {
+            pixel.copy_from_slice(&[color[0], color[1], color[2], 255]);
+            return;
+        }
+        let src_alpha = coverage;
+        let dst_alpha = pixel[3] as f32 / 255.0;
+        let out_alpha = src_alpha + dst_alpha * (1.0 - src_alpha);
+        if out_alpha <= 0.0 {
+            return;
+        }
+        for c in 0..3 {
+            let src = color[c] as f32 * src_alpha;
+            let dst = pixel[c] as f32 * dst_alpha * (1.0 - src_alpha);
+            pixel[c] = ((src + dst) / out_alpha).round() as u8;
+        }
+        pixel[3] = (out_alpha * 255.0).round() as u8;
+    }
+
+    fn fill(&mut self, min: Vec2, max: Vec2, color: [u8; 3], inside: impl Fn(Vec2) -> bool) {
+        let resolution = self.resolution;
+        let x0 = (min.x * resolution).floor().max(0.0) as u32;
+        let y0 = (min.y * resolution).floor().max(0.0) as u32;
+        let x1 = ((max.x * resolution).ceil() as u32).min(self.width);
+        let y1 = ((max.y * resolution).ceil() as u32).min(self.height);
+        let sample = |x: f32, y: f32| inside(Vec2::new(x, y) / resolution);
+        let corner_row =
+            |y: u32| -> Vec<bool> { (x0..=x1).map(|x| sample(x as f32, y as f32)).collect() };
+        let step = 1.0 / SUPERSAMPLE as f32;
+
+        let mut top = corner_row(y0);
+        for y in y0..y1 {
+            let bottom = corner_row(y + 1);
+            for x in x0..x1 {
+                let i = (x - x0) as usize;
+                let corners = [top[i], top[i + 1], bottom[i], bottom[i + 1]];
+                let coverage = if corners.iter().all(|c| *c) {
+                    1.0
+                } else if corners.iter().all(|c| !*c) {
+                    0.0
+                } else {
+                    let mut hits = 0;
+                    for sy in 0..SUPERSAMPLE {
+                        for sx in 0..SUPERSAMPLE {
+                            let px = x as f32 + (sx as f32 + 0.5) * step;
+                            let py = y as f32 + (sy as f32 + 0.5) * step;
+                            if sample(px, py) {
+                                hits += 1;
+                            }
+                        }
+                    }
+                    hits as f32 / (SUPERSAMPLE * SUPERSAMPLE) as f32
+                };
+                if coverage > 0.0 {
+                    self.blend(x, y, color, coverage);
+                }
+            }
+            top = bottom;
+        }
+    }
+
+    fn into_image(self) -> Image {
+        let mut image = Image::new(
+            Extent3d {
+                width: self.width,
+                height: self.height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            self.pixels,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        image.sampler = ImageSampler::linear();
+        image
+    }
+}
+
+fn card_size() -> Vec2 {
+    Vec2::new(CARD_WIDTH, CARD_HEIGHT)
+}
+
+fn in_rounded_rect(p: Vec2, min: Vec2, max: Vec2, radius: f32) -> bool {
+    let inner_min = min + Vec2::splat(radius);
+    let inner_max = max - Vec2::splat(radius);
+    let closest = p.clamp(inner_min, inner_max);
+    p.x >= min.x && p.y >= min.y && p.x <= max.x && p.y <= max.y && p.distance(closest) <= radius
+}
+
+fn in_circle(p: Vec2, center: Vec2, radius: f32) -> bool {
+    p.distance_squared(center) <= radius * radius
+}
+
+fn in_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    let side = |u: Vec2, v: Vec2| (v - u).perp_dot(p - u);
+    let (d1, d2, d3) = (side(a, b), side(b, c), side(c, a));
+    let has_neg = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_pos = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(has_neg && has_pos)
+}
+
+fn in_suit(suit: Suit, p: Vec2) -> bool {
+    match suit {
+        Suit::Diamonds => p.x.abs() / 0.72 + p.y.abs() <= 1.0,
+        Suit::Hearts => {
+            in_circle(p, Vec2::new(-0.45, -0.38), 0.5)
+                || in_circle(p, Vec2::new(0.45, -0.38), 0.5)
+                || in_triangle(
+                    p,
+                    Vec2::new(-0.93, -0.2),
+                    Vec2::new(0.93, -0.2),
+                    Vec2::new(0.0, 0.95),
+                )
+        }
+        Suit::Spades => {
+            in_circle(p, Vec2::new(-0.43, 0.2), 0.45)
+                || in_circle(p, Vec2::new(0.43, 0.2), 0.45)
+                || in_triangle(
+                    p,
+                    Vec2::new(-0.86, 0.05),
+                    Vec2::new(0.86, 0.05),
+                    Vec2::new(0.0, -0.95),
+                )
+                || in_triangle(
+                    p,
+                    Vec2::new(0.0, 0.3),
+                    Vec2::new(-0.32, 1.0),
+                    Vec2::new(0.32, 1.0),
+                )
+        }
+        Suit::Clubs => {
+            in_circle(p, Vec2::new(0.0, -0.48), 0.38)
+                || in_circle(p, Vec2::new(-0.46, 0.12), 0.38)
+                || in_circle(p, Vec2::new(0.46, 0.12), 0.38)
+                || in_circle(p, Vec2::new(0.0, 0.0), 0.22)
+                || in_triangle(
+                    p,
+                    Vec2::new(0.0, 0.1),
+                    Vec2::new(-0.32, 1.0),
+                    Vec2::new(0.32, 1.0),
+                )
+        }
+    }
+}
+
+fn glyph(c: char) -> [u8; 7] {
+    match c {
+        'A' => [
+            0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001,
+        ],
+        '2' => [
+            0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111,
+        ],
+        '3' => [
+            0b11110, 0b00001, 0b00001, 0b01110, 0b00001, 0b00001, 0b11110,
+        ],
+        '4' => [
+            0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010,
+        ],
+        '5' => [
+            0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110,
+        ],
+        '6' => [
+            0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110,
+        ],
+        '7' => [
+            0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000,
+        ],
+        '8' => [
+            0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110,
+        ],
+        '9' => [
+            0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100,
+        ],
+        '1' => [
+            0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110,
+        ],
+        '0' => [
+            0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110,
+        ],
+        'J' => [
+            0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100,
+        ],
+        'Q' => [
+            0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101,
+        ],
+        'K' => [
+            0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001,
+        ],
+        _ => [0; 7],
+    }
+}
+
+fn text_width(text: &str, cell: f32) -> f32 {
+    let count = text.chars().count() as f32;
+    count * 5.0 * cell + (count - 1.0).max(0.0) * cell
+}
+
+fn in_text(text: &str, origin: Vec2, cell: f32, p: Vec2) -> bool {
+    let local = (p - origin) / cell;
+    if local.x < 0.0 || local.y < 0.0 || local.y >= 7.0 {
+        return false;
+    }
+    let (column, row) = (local.x as usize, local.y as usize);
+    let (index, x) = (column / 6, column % 6);
+    if x == 5 {
+        return false;
+    }
+    text.chars()
+        .nth(index)
+        .is_some_and(|c| glyph(c)[row] & (0b10000 >> x) != 0)
+}
+
+struct Mark {
+    center: Vec2,
+    radius: f32,
+    flipped: bool,
+}
+
+fn pip_marks(rank: Rank) -> Vec<Mark> {
+    let [left, middle, right] = PIP_COLUMNS;
+    let rows: &[(f32, f32)] = match rank {
+        Rank::Ace => {
+            return vec![Mark {
+                center: card_size() / 2.0,
+                radius: 24.0,
+                flipped: false,
+            }]
+        }
+        Rank::Two => &[(middle, 0.0), (middle, 1.0)],
+        Rank::Three => &[(middle, 0.0), (middle, 0.5), (middle, 1.0)],
+        Rank::Four => &[(left, 0.0), (right, 0.0), (left, 1.0), (right, 1.0)],
+        Rank::Five => &[
+            (left, 0.0),
+            (right, 0.0),
+            (middle, 0.5),
+            (left, 1.0),
+            (right, 1.0),
+        ],
+        Rank::Six => &[
+            (left, 0.0),
+            (right, 0.0),
+            (left, 0.5),
+            (right, 0.5),
+            (left, 1.0),
+            (right, 1.0),
+        ],
+        Rank::Seven => &[
+            (left, 0.0),
+            (right, 0.0),
+            (middle, 0.25),
+            (left, 0.5),
+            (right, 0.5),
+            (left, 1.0),
+            (right, 1.0),
+        ],
+        Rank::Eight => &[
+            (left, 0.0),
+            (right, 0.0),
+            (middle, 0.25),
+            (left, 0.5),
+            (right, 0.5),
+            (middle, 0.75),
+            (left, 1.0),
+            (right, 1.0),
+        ],
+        Rank::Nine => &[
+            (left, 0.0),
+            (right, 0.0),
+            (left, 1.0 / 3.0),
+            (right, 1.0 / 3.0),
+            (middle, 0.5),
+            (left, 2.0 / 3.0),
+            (right, 2.0 / 3.0),
+            (left, 1.0),
+            (right, 1.0),
+        ],
+        Rank::Ten => &[
+            (left, 0.0),
+            (right, 0.0),
+            (middle, 1.0 / 6.0),
+            (left, 1.0 / 3.0),
+            (right, 1.0 / 3.0),
+            (left, 2.0 / 3.0),
+            (right, 2.0 / 3.0),
+            (middle, 5.0 / 6.0),
+            (left, 1.0),
+            (right, 1.0),
+        ],
+        Rank::Jack | Rank::Queen | Rank::King => &[(middle, 0.17), (middle, 0.83)],
+    };
+    rows.iter()
+        .map(|&(x, row)| Mark {
+            center: Vec2::new(x, PIP_TOP + row * (PIP_BOTTOM - PIP_TOP)),
+            radius: PIP_RADIUS,
+            flipped: row > 0.5,
+        })
+        .collect()
+}
+
+fn draw_pip(canvas: &mut Canvas, suit: Suit, color: [u8; 3], mark: &Mark) {
+    let extent = Vec2::splat(mark.radius);
+    let flip = if mark.flipped { -1.0 } else { 1.0 };
+    canvas.fill(mark.center - extent, mark.center + extent, color, |p| {
+        let local = (p - mark.center) / mark.radius;
+        in_suit(suit, Vec2::new(local.x, local.y * flip))
+    });
+}
+
+fn draw_index(canvas: &mut Canvas, suit: Suit, rank: Rank, style: CardStyle) {
+    let color = style.suit_color(suit);
+    let (cell, pip_radius) = style.index_size();
+    let center_x = 4.0 + 4.5 * cell;
+    let text = rank.as_str();
+    let width = text_width(text, cell);
+    let origin = Vec2::new(center_x - width / 2.0, 7.0);
+    let height = 7.0 * cell;
+    let pip_center = Vec2::new(center_x, origin.y + height + 3.5 + pip_radius);
+    let size = card_size();
+
+    for rotated in [false, true] {
+        let map = |p: Vec2| if rotated { size - p } else { p };
+        let (min, max) = if rotated {
+            (size - (origin + Vec2::new(width, height)), size - origin)
+        } else {
+            (origin, origin + Vec2::new(width, height))
+        };
+        canvas.fill(min, max, color, |p| in_text(text, origin, cell, map(p)));
+
+        let extent = Vec2::splat(pip_radius);
+        let center = map(pip_center);
+        canvas.fill(center - extent, center + extent, color, |p| {
+            let local = (map(p) - pip_center) / pip_radius;
+            in_suit(suit, local)
+        });
+    }
+}
+
+fn draw_court(canvas: &mut Canvas, rank: Rank, color: [u8; 3]) {
+    let min = Vec2::new(26.0, 20.0);
+    let max = card_size() - min;
+    let tint = color.map(|c| ((c as u16 + 4 * 250) / 5) as u8);
+    canvas.fill(min, max, color, |p| in_rounded_rect(p, min, max, 4.0));
+    let inset = Vec2::splat(2.0);
+    canvas.fill(min + inset, max - inset, tint, |p| {
+        in_rounded_rect(p, min + inset, max - inset, 3.0)
+    });
+
+    let text = rank.as_str();
+    let size = Vec2::new(text_width(text, COURT_CELL), 7.0 * COURT_CELL);
+    let origin = card_size() / 2.0 - size / 2.0;
+    canvas.fill(origin, origin + size, color, |p| {
+        in_text(text, origin, COURT_CELL, p)
+    });
+}
+
+fn draw_blank(canvas: &mut Canvas, edge: [u8; 3], fill: [u8; 3]) {
+    let size = card_size();
+    canvas.fill(Vec2::ZERO, size, edge, |p| {
+        in_rounded_rect(p, Vec2::ZERO, size, CORNER_RADIUS)
+    });
+    let inset = Vec2::splat(BORDER);
+    canvas.fill(inset, size - inset, fill, |p| {
+        in_rounded_rect(p, inset, size - inset, CORNER_RADIUS - BORDER)
+    });
+}
+
+fn draw_face_pattern(canvas: &mut Canvas) {
+    let min = Vec2::splat(2.5);
+    let max = card_size() - min;
+    canvas.fill(min, max, PATTERN_FACE, |p| {
+        let band = in_rounded_rect(p, min, max, CORNER_RADIUS - 2.5)
+            && !in_rounded_rect(p, min + 1.2, max - 1.2, CORNER_RADIUS - 3.7);
+        band && (p.x + p.y).rem_euclid(8.0) < 4.0
+    });
+}
+
+fn render_face(suit: Suit, rank: Rank, style: CardStyle, resolution: f32) -> Image {
+    let mut canvas = Canvas::new(resolution);
+    let color = style.suit_color(suit);
+    draw_blank(&mut canvas, style.edge_color(), style.face_color());
+    if style.patterns {
+        draw_face_pattern(&mut canvas);
+    }
+    if matches!(rank, Rank::Jack | Rank::Queen | Rank::King) {
+        draw_court(&mut canvas, rank, color);
+    }
+    for mark in pip_marks(rank) {
+        draw_pip(&mut canvas, suit, color, &mark);
+    }
+    draw_index(&mut canvas, suit, rank, style);
+    canvas.into_image()
+}
+
+fn render_back(style: CardStyle, resolution: f32) -> Image {
+    let mut canvas = Canvas::new(resolution);
+    draw_blank(&mut canvas, style.edge_color(), BACK);
+    let min = Vec2::splat(8.0);
+    let max = card_size() - min;
+    canvas.fill(min, max, BACK_LINE, |p| {
+        let inside = in_rounded_rect(p, min, max, 4.0);
+        let border = !in_rounded_rect(p, min + 1.5, max - 1.5, 3.0);
+        let lattice = (p.x + p.y).rem_euclid(12.0) < 1.5 || (p.x - p.y).rem_euclid(12.0) < 1.5;
+        inside && (border || (!style.patterns && lattice))
+    });
+    if style.patterns {
+        let inset = Vec2::splat(3.0);
+        canvas.fill(min + inset, max - inset, PATTERN_BACK, |p| {
+            let cell = ((p - min) / 13.0).floor();
+            in_rounded_rect(p, min + inset, max - inset, 2.0) && (cell.x + cell.y) as i32 % 2 == 0
+        });
+    }
+    canvas.into_image()
+}
+
+fn render_slot(resolution: f32) -> Image {
+    let mut canvas = Canvas::new(resolution);
+    let size = card_size();
+    let inset = Vec2::splat(BORDER * 2.0);
+    canvas.fill(Vec2::ZERO, size, SLOT_EDGE, |p| {
+        in_rounded_rect(p, Vec2::ZERO, size, CORNER_RADIUS)
+            && !in_rounded_rect(p, inset, size - inset, CORNER_RADIUS - BORDER * 2.0)
+    });
+    canvas.into_image()
+}
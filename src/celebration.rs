@@ -0,0 +1,197 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::atlas::CardAtlas;
+use crate::layout::{get_pile_position, ColumnShape, CARD_HEIGHT, CARD_WIDTH};
+use crate::models::{Card, Rank};
+use crate::resources::{GameAssets, GameState, PileType, SpiderGame};
+use crate::summary::ShowSummary;
+
+const LAUNCH_INTERVAL: f32 = 0.08;
+const TRAIL_INTERVAL: f32 = 0.03;
+const GRAVITY: f32 = 2400.0;
+const BOUNCE: f32 = 0.75;
+const TRAIL_Z: f32 = 600.0;
+const LIVE_Z: f32 = 990.0;
+
+pub struct CelebrationPlugin;
+
+impl Plugin for CelebrationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Won), start_celebration)
+            .add_systems(
+                Update,
+                (launch_cards, bounce_cards, finish_celebration)
+                    .chain()
+                    .run_if(in_state(GameState::Won).and(resource_exists::<Celebration>)),
+            )
+            .add_systems(OnExit(GameState::Won), cleanup_celebration);
+    }
+}
+
+#[derive(Resource)]
+struct Celebration {
+    pending: Vec<(Card, Vec3)>,
+    launch_timer: Timer,
+    scale: f32,
+    next_trail_z: f32,
+}
+
+#[derive(Component)]
+struct CelebrationEntity;
+
+#[derive(Component)]
+struct BouncingCard {
+    velocity: Vec2,
+    trail_timer: Timer,
+}
+
+fn start_celebration(mut commands: Commands, game: Res<SpiderGame>, window_query: Query<&Window>) {
+    let Some(window) = window_query.iter().next() else {
+        return;
+    };
+    let window_size = Vec2::new(window.width(), window.height());
+
+    let mut pending = Vec::new();
+    let mut scale = 1.0;
+    for rank in Rank::all().into_iter().rev() {
+        for (i, king) in game.foundations.iter().enumerate() {
+            let (pos, pile_scale) = get_pile_position(
+                PileType::Foundation(i),
+                0,
+                window_size,
+                ColumnShape::loose(1),
+            );
+            scale = pile_scale;
+            pending.push((
+                Card {
+                    suit: king.suit,
+                    rank,
+                    face_up: true,
+                },
+                pos,
+            ));
+        }
+    }
+    pending.reverse();
+
+    commands.insert_resource(Celebration {
+        pending,
+        launch_timer: Timer::from_seconds(LAUNCH_INTERVAL, TimerMode::Repeating),
+        scale,
+        next_trail_z: TRAIL_Z,
+    });
+}
+
+fn launch_cards(
+    mut commands: Commands,
+    time: Res<Time>,
+    game_assets: Res<GameAssets>,
+    atlas: Res<CardAtlas>,
+    mut celebration: ResMut<Celebration>,
+    bouncing: Query<(), With<BouncingCard>>,
+) {
+    celebration.launch_timer.tick(time.delta());
+    if !celebration.launch_timer.just_finished() && !bouncing.is_empty() {
+        return;
+    }
+    let Some((card, pos)) = celebration.pending.pop() else {
+        return;
+    };
+
+    let mut rng = rand::thread_rng();
+    let inward = if pos.x > 0.0 { -1.0 } else { 1.0 };
+    let direction = if rng.gen_bool(0.2) { -inward } else { inward };
+    let velocity = Vec2::new(
+        direction * rng.gen_range(150.0..450.0),
+        rng.gen_range(0.0..600.0),
+    ) * celebration.scale;
+
+    commands.spawn((
+        atlas.card_sprite(&card, &game_assets),
+        Transform {
+            translation: Vec3::new(pos.x, pos.y, LIVE_Z),
+            scale: Vec3::splat(celebration.scale),
+            ..default()
+        },
+        BouncingCard {
+            velocity,
+            trail_timer: Timer::from_seconds(TRAIL_INTERVAL, TimerMode::Repeating),
+        },
+        CelebrationEntity,
+    ));
+}
+
+fn bounce_cards(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut celebration: ResMut<Celebration>,
+    window_query: Query<&Window>,
+    mut query: Query<(Entity, &mut Transform, &mut BouncingCard, &Sprite)>,
+) {
+    let Some(window) = window_query.iter().next() else {
+        return;
+    };
+    let half = Vec2::new(window.width(), window.height()) / 2.0;
+    let scale = celebration.scale;
+    let floor = -half.y + CARD_HEIGHT * scale / 2.0;
+    let dt = time.delta_secs();
+
+    for (entity, mut transform, mut bouncing, sprite) in query.iter_mut() {
+        bouncing.velocity.y -= GRAVITY * scale * dt;
+        transform.translation.x += bouncing.velocity.x * dt;
+        transform.translation.y += bouncing.velocity.y * dt;
+        if transform.translation.y < floor {
+            transform.translation.y = floor;
+            bouncing.velocity.y = -bouncing.velocity.y * BOUNCE;
+        }
+
+        if bouncing.trail_timer.tick(time.delta()).just_finished() {
+            commands.spawn((
+                sprite.clone(),
+                Transform {
+                    translation: transform.translation.with_z(celebration.next_trail_z),
+                    scale: transform.scale,
+                    ..default()
+                },
+                CelebrationEntity,
+            ));
+            celebration.next_trail_z += 0.002;
+        }
+
+        if transform.translation.x.abs() > half.x + CARD_WIDTH * scale {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn finish_celebration(
+    mut commands: Commands,
+    celebration: Res<Celebration>,
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut summary_writer: MessageWriter<ShowSummary>,
+    bouncing: Query<(), With<BouncingCard>>,
+    entities: Query<Entity, With<CelebrationEntity>>,
+) {
+    let skipped = mouse_button.just_pressed(MouseButton::Left)
+        || keyboard.any_just_pressed([KeyCode::Space, KeyCode::Enter]);
+    let done = celebration.pending.is_empty() && bouncing.is_empty();
+    if !skipped && !done {
+        return;
+    }
+    if skipped {
+        for entity in entities.iter() {
+            commands.entity(entity).despawn();
+        }
+    }
+    commands.remove_resource::<Celebration>();
+    summary_writer.write(ShowSummary);
+}
+
+fn cleanup_celebration(mut commands: Commands, query: Query<Entity, With<CelebrationEntity>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<Celebration>();
+}
@@ -0,0 +1,477 @@
+use bevy::prelude::*;
+use chrono::{Datelike, Local, Months, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::resources::{Difficulty, GameAssets, GameMode, GameState, SpiderGame};
+use crate::scoring::ScoringScheme;
+use crate::storage;
+use crate::systems::DealEvent;
+use crate::theme::ThemeBackground;
+
+pub const DAILY_RESULTS_FILE: &str = "daily.json";
+
+pub struct DailyPlugin;
+
+impl Plugin for DailyPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(storage::load::<DailyResults>(DAILY_RESULTS_FILE))
+            .init_resource::<CalendarView>()
+            .add_systems(OnEnter(GameState::DailyCalendar), reset_calendar_view)
+            .add_systems(
+                Update,
+                (handle_calendar_interaction, refresh_calendar)
+                    .chain()
+                    .run_if(in_state(GameState::DailyCalendar)),
+            )
+            .add_systems(OnExit(GameState::DailyCalendar), cleanup_calendar)
+            .add_systems(OnExit(GameState::Playing), record_daily_result);
+    }
+}
+
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+pub fn daily_date() -> NaiveDate {
+    Utc::now().date_naive()
+}
+
+pub fn daily_seed(date: NaiveDate, difficulty: Difficulty) -> u64 {
+    let mut z = ((date.num_days_from_ce() as u64) << 8 | difficulty as u64)
+        .wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DailyResult {
+    #[serde(default)]
+    pub scoring: ScoringScheme,
+    pub score: i32,
+    pub moves: u32,
+    pub time_secs: u32,
+    pub completed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DailyRecord {
+    date: NaiveDate,
+    difficulty: Difficulty,
+    result: DailyResult,
+}
+
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub struct DailyResults {
+    records: Vec<DailyRecord>,
+}
+
+impl DailyResults {
+    pub fn get(&self, date: NaiveDate, difficulty: Difficulty) -> Option<&DailyResult> {
+        self.records
+            .iter()
+            .find(|r| r.date == date && r.difficulty == difficulty)
+            .map(|r| &r.result)
+    }
+
+    pub fn record(&mut self, date: NaiveDate, difficulty: Difficulty, result: DailyResult) {
+        let Some(existing) = self
+            .records
+            .iter_mut()
+            .find(|r| r.date == date && r.difficulty == difficulty)
+        else {
+            self.records.push(DailyRecord {
+                date,
+                difficulty,
+                result,
+            });
+            return;
+        };
+
+        let old = existing.result;
+        let is_better = match (old.completed, result.completed) {
+            (false, true) => true,
+            (true, false) => false,
+            _ if result.scoring == old.scoring => result.score > old.score,
+            _ => (result.moves, result.time_secs) < (old.moves, old.time_secs),
+        };
+        if is_better {
+            existing.result = result;
+        }
+    }
+
+    pub fn record_game(&mut self, game: &SpiderGame) -> bool {
+        let GameMode::Daily(date) = game.mode else {
+            return false;
+        };
+        if game.move_count == 0 && !game.is_won() {
+            return false;
+        }
+        self.record(
+            date,
+            game.difficulty,
+            DailyResult {
+                scoring: game.scoring,
+                score: game.score,
+                moves: game.move_count,
+                time_secs: game.elapsed_secs as u32,
+                completed: game.is_won(),
+            },
+        );
+        true
+    }
+}
+
+fn record_daily_result(game: Res<SpiderGame>, mut results: ResMut<DailyResults>) {
+    if results.record_game(&game) {
+        storage::save(DAILY_RESULTS_FILE, &*results);
+    }
+}
+
+#[derive(Resource)]
+pub struct CalendarView {
+    pub first_of_month: NaiveDate,
+    pub difficulty: Difficulty,
+}
+
+impl Default for CalendarView {
+    fn default() -> Self {
+        Self {
+            first_of_month: daily_date().with_day(1).unwrap(),
+            difficulty: Difficulty::Easy,
+        }
+    }
+}
+
+#[derive(Component)]
+struct CalendarEntity;
+
+#[derive(Component, Clone, Copy)]
+enum CalendarButton {
+    Difficulty(Difficulty),
+    PreviousMonth,
+    NextMonth,
+    Day(NaiveDate),
+    Back,
+}
+
+fn reset_calendar_view(mut view: ResMut<CalendarView>) {
+    view.first_of_month = daily_date().with_day(1).unwrap();
+}
+
+fn handle_calendar_interaction(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut deal_writer: MessageWriter<DealEvent>,
+    mut view: ResMut<CalendarView>,
+    query: Query<(&Interaction, &CalendarButton), Changed<Interaction>>,
+) {
+    for (interaction, button) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *button {
+            CalendarButton::Difficulty(diff) => view.difficulty = diff,
+            CalendarButton::PreviousMonth => {
+                view.first_of_month = view.first_of_month - Months::new(1);
+            }
+            CalendarButton::NextMonth => {
+                if view.first_of_month < daily_date().with_day(1).unwrap() {
+                    view.first_of_month = view.first_of_month + Months::new(1);
+                }
+            }
+            CalendarButton::Day(date) => {
+                deal_writer.write(DealEvent(view.difficulty, GameMode::Daily(date)));
+                next_state.set(GameState::Playing);
+            }
+            CalendarButton::Back => next_state.set(GameState::Menu),
+        }
+    }
+}
+
+fn cleanup_calendar(mut commands: Commands, query: Query<Entity, With<CalendarEntity>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn refresh_calendar(
+    mut commands: Commands,
+    view: Res<CalendarView>,
+    results: Res<DailyResults>,
+    game_assets: Res<GameAssets>,
+    existing: Query<Entity, With<CalendarEntity>>,
+) {
+    if !view.is_changed() && !results.is_changed() && !existing.is_empty() {
+        return;
+    }
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let font = game_assets.font.clone();
+    let text = |label: String, size: f32| {
+        (
+            Text::new(label),
+            TextFont {
+                font: font.clone(),
+                font_size: size,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+        )
+    };
+
+    let today = daily_date();
+    let first = view.first_of_month;
+    let days_in_month = (first + Months::new(1))
+        .signed_duration_since(first)
+        .num_days() as u32;
+    let leading_blanks = first.weekday().num_days_from_monday();
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(12.0),
+                ..default()
+            },
+            BackgroundColor(game_assets.background),
+            ThemeBackground,
+            CalendarEntity,
+        ))
+        .with_children(|parent| {
+            parent.spawn(text("Daily Challenge".to_string(), 60.0));
+
+            parent
+                .spawn(Node {
+                    column_gap: Val::Px(10.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    for diff in Difficulty::all() {
+                        let color = if diff == view.difficulty {
+                            Color::srgb(222. / 255., 154. / 255., 40. / 255.)
+                        } else {
+                            Color::srgba(0.0, 0.0, 0.0, 0.3)
+                        };
+                        row.spawn((
+                            Button,
+                            Node {
+                                width: Val::Px(180.0),
+                                height: Val::Px(45.0),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor(color),
+                            CalendarButton::Difficulty(diff),
+                        ))
+                        .with_children(|b| {
+                            b.spawn(text(diff.label().to_string(), 20.0));
+                        });
+                    }
+                });
+
+            parent
+                .spawn(Node {
+                    column_gap: Val::Px(20.0),
+                    align_items: AlignItems::Center,
+                    ..default()
+                })
+                .with_children(|row| {
+                    let arrow = |label: &str, button: CalendarButton| {
+                        (
+                            Button,
+                            Node {
+                                width: Val::Px(50.0),
+                                height: Val::Px(45.0),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.3)),
+                            button,
+                            children![text(label.to_string(), 25.0)],
+                        )
+                    };
+                    row.spawn(arrow("<", CalendarButton::PreviousMonth));
+                    row.spawn((
+                        Node {
+                            width: Val::Px(300.0),
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        children![text(first.format("%B %Y").to_string(), 30.0)],
+                    ));
+                    row.spawn(arrow(">", CalendarButton::NextMonth));
+                });
+
+            parent
+                .spawn(Node {
+                    display: Display::Grid,
+                    grid_template_columns: RepeatedGridTrack::px(7, 80.0),
+                    row_gap: Val::Px(6.0),
+                    column_gap: Val::Px(6.0),
+                    ..default()
+                })
+                .with_children(|grid| {
+                    for weekday in ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"] {
+                        grid.spawn((
+                            Node {
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            children![text(weekday.to_string(), 18.0)],
+                        ));
+                    }
+                    for _ in 0..leading_blanks {
+                        grid.spawn(Node::default());
+                    }
+                    for day in 1..=days_in_month {
+                        let date = first.with_day(day).unwrap();
+                        let result = results.get(date, view.difficulty);
+                        let background = match result {
+                            _ if date > today => Color::srgba(0.0, 0.0, 0.0, 0.1),
+                            Some(r) if r.completed => {
+                                Color::srgb(222. / 255., 154. / 255., 40. / 255.)
+                            }
+                            Some(_) => Color::srgb(229. / 255., 93. / 255., 77. / 255.),
+                            None => Color::srgba(0.0, 0.0, 0.0, 0.3),
+                        };
+                        let mut cell = grid.spawn((
+                            Node {
+                                height: Val::Px(60.0),
+                                flex_direction: FlexDirection::Column,
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                border: UiRect::all(Val::Px(3.0)),
+                                ..default()
+                            },
+                            BackgroundColor(background),
+                            BorderColor::all(if date == today {
+                                Color::WHITE
+                            } else {
+                                Color::NONE
+                            }),
+                        ));
+                        if date <= today {
+                            cell.insert((Button, CalendarButton::Day(date)));
+                        }
+                        cell.with_children(|c| {
+                            c.spawn(text(day.to_string(), 22.0));
+                            if let Some(r) = result.filter(|r| r.completed) {
+                                c.spawn(text(r.scoring.format_score(r.score), 14.0));
+                            }
+                        });
+                    }
+                });
+
+            parent
+                .spawn(Node {
+                    column_gap: Val::Px(20.0),
+                    align_items: AlignItems::Center,
+                    ..default()
+                })
+                .with_children(|row| {
+                    for (label, color) in [
+                        ("Solved", Color::srgb(222. / 255., 154. / 255., 40. / 255.)),
+                        (
+                            "Attempted",
+                            Color::srgb(229. / 255., 93. / 255., 77. / 255.),
+                        ),
+                    ] {
+                        row.spawn((
+                            Node {
+                                width: Val::Px(20.0),
+                                height: Val::Px(20.0),
+                                ..default()
+                            },
+                            BackgroundColor(color),
+                        ));
+                        row.spawn(text(label.to_string(), 18.0));
+                    }
+                });
+
+            parent
+                .spawn(Node {
+                    column_gap: Val::Px(20.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    for (label, button, color) in [
+                        (
+                            "Play Today",
+                            CalendarButton::Day(today),
+                            Color::srgb(142. / 255., 146. / 255., 87. / 255.),
+                        ),
+                        ("Back", CalendarButton::Back, Color::srgb(0.4, 0.4, 0.4)),
+                    ] {
+                        row.spawn((
+                            Button,
+                            Node {
+                                width: Val::Px(250.0),
+                                height: Val::Px(60.0),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor(color),
+                            button,
+                        ))
+                        .with_children(|b| {
+                            b.spawn(text(label.to_string(), 25.0));
+                        });
+                    }
+                });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(scoring: ScoringScheme, score: i32, moves: u32) -> DailyResult {
+        DailyResult {
+            scoring,
+            score,
+            moves,
+            time_secs: 300,
+            completed: true,
+        }
+    }
+
+    fn kept(first: DailyResult, second: DailyResult) -> DailyResult {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let mut results = DailyResults::default();
+        results.record(date, Difficulty::Easy, first);
+        results.record(date, Difficulty::Easy, second);
+        *results.get(date, Difficulty::Easy).unwrap()
+    }
+
+    #[test]
+    fn scores_are_compared_within_one_scheme() {
+        let kept = kept(
+            result(ScoringScheme::Standard, 600, 120),
+            result(ScoringScheme::Standard, 650, 140),
+        );
+        assert_eq!(kept.score, 650);
+    }
+
+    #[test]
+    fn different_schemes_are_ranked_by_moves() {
+        let replaced = kept(
+            result(ScoringScheme::Standard, 600, 120),
+            result(ScoringScheme::Vegas, -50, 100),
+        );
+        assert_eq!(replaced.scoring, ScoringScheme::Vegas);
+        let unchanged = kept(
+            result(ScoringScheme::Relaxed, 0, 100),
+            result(ScoringScheme::Windows, 700, 120),
+        );
+        assert_eq!(unchanged.scoring, ScoringScheme::Relaxed);
+    }
+}
@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::models::{Card, Rank, Suit};
+use crate::resources::{
+    AnimationEventQueue, GameHistory, GameState, Landing, PileType, SpiderGame, StartAnimationEvent,
+};
+use crate::ui::{ClickableCard, MovingCard};
+
+const VIEW_GRACE_FRAMES: u32 = 3;
+
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugOverlay>()
+            .add_systems(Startup, setup_debug_overlay)
+            .add_systems(Update, toggle_debug_overlay)
+            .add_systems(
+                Last,
+                (
+                    check_cards.run_if(in_state(GameState::Playing)),
+                    update_debug_overlay,
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct DebugOverlay {
+    pub visible: bool,
+    census: CardCensus,
+    violations: Vec<String>,
+}
+
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct CardCensus {
+    pub tableau: usize,
+    pub stock: usize,
+    pub foundations: usize,
+    pub in_flight: usize,
+    pub queued: usize,
+    pub history: usize,
+    pub expected: usize,
+}
+
+impl CardCensus {
+    pub fn total(&self) -> usize {
+        self.tableau + self.stock + self.foundations * 13
+    }
+}
+
+#[derive(Component)]
+struct DebugText;
+
+fn card_code(card: &Card) -> String {
+    let code = format!("{}{}", card.rank.as_str(), card.suit.as_str());
+    if card.face_up {
+        code
+    } else {
+        format!("[{}]", code)
+    }
+}
+
+pub fn model_violations(game: &SpiderGame) -> Vec<String> {
+    let deck = Card::new_spider_deck(game.difficulty);
+    let mut violations = Vec::new();
+    let total = game.tableau.iter().map(Vec::len).sum::<usize>()
+        + game.stock.len()
+        + game.foundations.len() * 13;
+    if total != deck.len() {
+        violations.push(format!("{} cards in play, expected {}", total, deck.len()));
+    }
+
+    for (col, column) in game.tableau.iter().enumerate() {
+        let hidden = column.iter().take_while(|card| !card.face_up).count();
+        if column[hidden..].iter().any(|card| !card.face_up) {
+            violations.push(format!(
+                "column {} has a face-down card above a face-up one",
+                col + 1
+            ));
+        }
+        if column.last().is_some_and(|top| !top.face_up) {
+            violations.push(format!("column {} has its top card face down", col + 1));
+        }
+    }
+
+    let mut missing: HashMap<(Suit, Rank), isize> = HashMap::new();
+    for card in &deck {
+        *missing.entry((card.suit, card.rank)).or_default() += 1;
+    }
+    for card in game.tableau.iter().flatten().chain(game.stock.iter()) {
+        *missing.entry((card.suit, card.rank)).or_default() -= 1;
+    }
+    for king in &game.foundations {
+        if king.rank != Rank::King {
+            violations.push(format!("foundation holds {}", card_code(king)));
+        }
+        for rank in Rank::all() {
+            *missing.entry((king.suit, rank)).or_default() -= 1;
+        }
+    }
+    let mut counts: Vec<_> = missing.into_iter().filter(|(_, n)| *n != 0).collect();
+    counts.sort_by_key(|((suit, rank), _)| (suit.as_str().to_string(), rank.value()));
+    for ((suit, rank), n) in counts {
+        let card = Card::new(suit, rank).name();
+        if n > 0 {
+            violations.push(format!("{} missing {} time(s)", card, n));
+        } else {
+            violations.push(format!("{} duplicated {} time(s)", card, -n));
+        }
+    }
+    violations
+}
+
+pub fn check_model(game: &SpiderGame) -> Result<(), String> {
+    let violations = model_violations(game);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations.join("\n"))
+    }
+}
+
+pub fn view_violations(
+    game: &SpiderGame,
+    in_flight: &[Landing],
+    shown: &[(PileType, usize, Card)],
+) -> Vec<String> {
+    let mut violations = Vec::new();
+    let mut seen: HashMap<(PileType, usize), usize> = HashMap::new();
+    for &(pile, index, card) in shown {
+        *seen.entry((pile, index)).or_default() += 1;
+        let model = match pile {
+            PileType::Tableau(col) => game.tableau.get(col).and_then(|pile| pile.get(index)),
+            PileType::Foundation(i) => game.foundations.get(i).filter(|_| index == 0),
+            PileType::Stock => continue,
+        };
+        match model {
+            Some(model) if *model == card => {}
+            Some(model) => violations.push(format!(
+                "{:?} #{} shows {} but the game has {}",
+                pile,
+                index,
+                card_code(&card),
+                card_code(model)
+            )),
+            None => violations.push(format!(
+                "{:?} #{} shows {} but the game has no card there",
+                pile,
+                index,
+                card_code(&card)
+            )),
+        }
+    }
+
+    let dragged = |pile: PileType, index: usize| {
+        game.drag_pos.is_some()
+            && game
+                .selected
+                .as_ref()
+                .is_some_and(|selected| selected.from == pile && index >= selected.index)
+    };
+    let slots = game
+        .tableau
+        .iter()
+        .enumerate()
+        .flat_map(|(col, cards)| (0..cards.len()).map(move |i| (PileType::Tableau(col), i)))
+        .chain((0..game.foundations.len()).map(|i| (PileType::Foundation(i), 0)));
+    for (pile, index) in slots {
+        let count = seen.get(&(pile, index)).copied().unwrap_or(0);
+        let flying = in_flight.iter().any(|landing| landing.covers(pile, index));
+        if count > 1 {
+            violations.push(format!("{:?} #{} is drawn {} times", pile, index, count));
+        } else if count == 0 && !flying && !dragged(pile, index) {
+            violations.push(format!("{:?} #{} is not drawn anywhere", pile, index));
+        }
+    }
+    violations
+}
+
+fn dump(
+    game: &SpiderGame,
+    census: &CardCensus,
+    queue: &[StartAnimationEvent],
+    in_flight: &[Landing],
+) -> String {
+    let mut lines = vec![format!(
+        "tableau {} + stock {} + foundations {}x13 = {} of {} ({} in flight, {} queued, history {})",
+        census.tableau,
+        census.stock,
+        census.foundations,
+        census.total(),
+        census.expected,
+        census.in_flight,
+        census.queued,
+        census.history
+    )];
+    for (col, cards) in game.tableau.iter().enumerate() {
+        let codes: Vec<String> = cards.iter().map(card_code).collect();
+        lines.push(format!("column {}: {}", col + 1, codes.join(" ")));
+    }
+    let stock: Vec<String> = game.stock.iter().map(card_code).collect();
+    lines.push(format!("stock: {}", stock.join(" ")));
+    let foundations: Vec<String> = game.foundations.iter().map(card_code).collect();
+    lines.push(format!("foundations: {}", foundations.join(" ")));
+    for ev in queue.iter().rev() {
+        let cards: Vec<String> = ev.cards.iter().map(card_code).collect();
+        lines.push(format!(
+            "queued: {} {:?} #{} -> {:?} #{:?}",
+            cards.join(" "),
+            ev.from,
+            ev.from_index,
+            ev.to,
+            ev.target_index_override
+        ));
+    }
+    for landing in in_flight {
+        lines.push(format!(
+            "landing: {} card(s) at {:?} #{}",
+            landing.count, landing.pile, landing.index
+        ));
+    }
+    if let Some(selected) = &game.selected {
+        lines.push(format!(
+            "selected: {:?} #{} ({})",
+            selected.from,
+            selected.index,
+            card_code(&selected.card)
+        ));
+    }
+    lines.join("\n")
+}
+
+#[allow(clippy::too_many_arguments)]
+fn check_cards(
+    game: Res<SpiderGame>,
+    queue_res: Res<AnimationEventQueue>,
+    history: Res<GameHistory>,
+    moving_cards: Query<&MovingCard>,
+    cards: Query<(&ClickableCard, &Card)>,
+    mut overlay: ResMut<DebugOverlay>,
+    mut view_failures: Local<u32>,
+    mut logged: Local<Vec<String>>,
+) {
+    if !cfg!(debug_assertions) && !overlay.visible {
+        return;
+    }
+    let moving: Vec<&MovingCard> = moving_cards.iter().filter(|m| !m.finished).collect();
+    let in_flight: Vec<Landing> = queue_res
+        .queue
+        .iter()
+        .map(StartAnimationEvent::landing)
+        .chain(moving.iter().map(|m| m.landing()))
+        .collect();
+    let census = CardCensus {
+        tableau: game.tableau.iter().map(Vec::len).sum(),
+        stock: game.stock.len(),
+        foundations: game.foundations.len(),
+        in_flight: moving.iter().map(|m| m.cards.len()).sum(),
+        queued: queue_res.queue.iter().map(|ev| ev.cards.len()).sum(),
+        history: history.stack.len(),
+        expected: Card::new_spider_deck(game.difficulty).len(),
+    };
+    if census.total() == 0 {
+        return;
+    }
+
+    let mut violations = model_violations(&game);
+    let shown: Vec<(PileType, usize, Card)> = cards
+        .iter()
+        .map(|(clickable, card)| (clickable.pile_type, clickable.index, *card))
+        .collect();
+    let view = view_violations(&game, &in_flight, &shown);
+    if view.is_empty() {
+        *view_failures = 0;
+    } else {
+        *view_failures += 1;
+        if *view_failures > VIEW_GRACE_FRAMES {
+            violations.extend(view);
+        }
+    }
+
+    if !violations.is_empty() && *logged != violations {
+        error!(
+            "Card check failed:\n{}\n{}",
+            violations.join("\n"),
+            dump(&game, &census, &queue_res.queue, &in_flight)
+        );
+    }
+    *logged = violations.clone();
+    if overlay.census != census || overlay.violations != violations {
+        overlay.census = census;
+        overlay.violations = violations;
+    }
+}
+
+fn setup_debug_overlay(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(70.0),
+            right: Val::Px(20.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        GlobalZIndex(1000),
+        Visibility::Hidden,
+        DebugText,
+    ));
+}
+
+fn toggle_debug_overlay(keyboard: Res<ButtonInput<KeyCode>>, mut overlay: ResMut<DebugOverlay>) {
+    if keyboard.just_pressed(KeyCode::F3) {
+        overlay.visible = !overlay.visible;
+    }
+}
+
+fn update_debug_overlay(
+    overlay: Res<DebugOverlay>,
+    state: Res<State<GameState>>,
+    mut text: Query<(&mut Text, &mut Visibility, &mut TextColor), With<DebugText>>,
+) {
+    if !overlay.is_changed() && !state.is_changed() {
+        return;
+    }
+    let Ok((mut text, mut visibility, mut color)) = text.single_mut() else {
+        return;
+    };
+    *visibility = if overlay.visible && *state.get() == GameState::Playing {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    let census = &overlay.census;
+    let mut lines = vec![
+        format!("Tableau: {}", census.tableau),
+        format!("Stock: {}", census.stock),
+        format!("Foundations: {} x 13", census.foundations),
+        format!("Total: {} / {}", census.total(), census.expected),
+        format!("In flight: {}", census.in_flight),
+        format!("Queued: {}", census.queued),
+        format!("History: {}", census.history),
+    ];
+    lines.extend(overlay.violations.iter().cloned());
+    **text = lines.join("\n");
+    color.0 = if overlay.violations.is_empty() {
+        Color::WHITE
+    } else {
+        Color::srgb(1.0, 0.4, 0.4)
+    };
+}
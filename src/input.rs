@@ -1,39 +1,62 @@
 use bevy::prelude::*;
 
-use crate::layout::{get_pile_position, CARD_HEIGHT, CARD_WIDTH};
-use crate::resources::{GameHistory, GameSounds, GameState, PileType, SelectedCard, SpiderGame};
-use crate::systems::{DealStockEvent, UndoEvent};
-use crate::ui::{ClickableCard, ClickableEmptyPile};
+use crate::animation::{fast_forward_animations, AnimationPace};
+use crate::layout::{CARD_HEIGHT, CARD_WIDTH};
+use crate::resources::{GameState, PileType, SelectedCard, SpiderGame};
+use crate::sound::{PlaySound, SoundCue};
+use crate::systems::{DealStockEvent, InvalidMoveEvent, MoveEvent, UndoEvent};
+use crate::ui::{CardTarget, ClickableCard, ClickableEmptyPile};
 
 pub struct InputPlugin;
 
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (handle_mouse_input, handle_keyboard_input));
+        app.add_systems(
+            Update,
+            (
+                handle_mouse_input.after(fast_forward_animations),
+                handle_keyboard_input,
+            ),
+        );
+    }
+}
+
+#[derive(Default)]
+struct BufferedPress(Option<Vec2>);
+
+impl BufferedPress {
+    fn take(&mut self, busy: bool, just_pressed: bool, cursor: Vec2) -> Option<Vec2> {
+        if just_pressed {
+            self.0 = Some(cursor);
+        }
+        if busy {
+            None
+        } else {
+            self.0.take()
+        }
     }
 }
 
 #[allow(clippy::too_many_arguments)]
 fn handle_mouse_input(
-    mut commands: Commands,
     mouse_button: Res<ButtonInput<MouseButton>>,
     windows: Query<&Window>,
     camera_query: Query<(&Camera, &GlobalTransform)>,
-    card_query: Query<(&Transform, &ClickableCard)>,
-    empty_query: Query<(&Transform, &ClickableEmptyPile)>,
+    card_query: Query<(&CardTarget, &ClickableCard)>,
+    empty_query: Query<(&CardTarget, &ClickableEmptyPile)>,
     mut game: ResMut<SpiderGame>,
-    mut history: ResMut<GameHistory>,
     state: Res<State<GameState>>,
     mut deal_stock_writer: MessageWriter<DealStockEvent>,
-    game_sounds: Option<Res<GameSounds>>,
+    mut invalid_writer: MessageWriter<InvalidMoveEvent>,
+    mut move_writer: MessageWriter<MoveEvent>,
+    mut sound_writer: MessageWriter<PlaySound>,
+    pace: Res<AnimationPace>,
+    mut buffered: Local<BufferedPress>,
 ) {
     if *state.get() != GameState::Playing {
+        buffered.0 = None;
         return;
     }
-    if game.auto_move_to.is_some() {
-        return;
-    }
-
     let Some(window) = windows.iter().next() else {
         return;
     };
@@ -49,7 +72,16 @@ fn handle_mouse_input(
         return;
     };
 
-    let window_size = Vec2::new(window.width(), window.height());
+    let just_pressed = mouse_button.just_pressed(MouseButton::Left);
+    let press = buffered
+        .take(pace.skipping, just_pressed, cursor_pos)
+        .and_then(|pos| camera.viewport_to_world_2d(camera_transform, pos).ok());
+    if pace.skipping {
+        return;
+    }
+    let replayed = press.is_some() && !just_pressed;
+    let released = mouse_button.just_released(MouseButton::Left)
+        || (replayed && !mouse_button.pressed(MouseButton::Left));
 
     if mouse_button.pressed(MouseButton::Left)
         && game.selected.is_some()
@@ -59,18 +91,17 @@ fn handle_mouse_input(
         game.drag_pos = Some(world_pos + offset);
     }
 
-    if mouse_button.just_pressed(MouseButton::Left) {
+    if let Some(press_pos) = press {
         let mut cards_with_distance: Vec<_> = card_query
             .iter()
             .map(|(t, c)| {
-                let scale = t.scale.x;
                 (
                     t.translation.z,
                     c,
-                    (world_pos.x - t.translation.truncate().x).abs(),
-                    (world_pos.y - t.translation.truncate().y).abs(),
+                    (press_pos.x - t.translation.x).abs(),
+                    (press_pos.y - t.translation.y).abs(),
                     t.translation.truncate(),
-                    scale,
+                    t.scale,
                 )
             })
             .filter(|(_, _, dx, dy, _, scale)| {
@@ -119,26 +150,30 @@ fn handle_mouse_input(
                     }
 
                     if valid_sequence {
-                        let offset = *card_pos - world_pos;
+                        let offset = *card_pos - press_pos;
                         game.drag_offset = Some(offset);
                         game.selected = Some(SelectedCard {
                             card: c,
                             from: clickable.pile_type,
                             index: clickable.index,
                         });
-                        game.drag_pos = Some(world_pos + offset);
+                        game.drag_pos = Some(press_pos + offset);
+                        sound_writer.write(PlaySound(SoundCue::PickUp));
+                    } else {
+                        invalid_writer.write(InvalidMoveEvent { card: c, to: None });
                     }
                 }
             }
         }
     }
 
-    if mouse_button.just_released(MouseButton::Left) {
+    if released {
         if let Some(selected) = &game.selected {
             let mut target_pile = None;
+            let mut rejected_pile = None;
             let mut min_dist = f32::MAX;
 
-            for (transform, card) in card_query.iter() {
+            for (target, card) in card_query.iter() {
                 if card.pile_type == selected.from {
                     continue;
                 }
@@ -151,15 +186,15 @@ fn handle_mouse_input(
                 };
 
                 if is_top_card {
-                    let scale = transform.scale.x;
+                    let scale = target.scale;
                     let half_w = (CARD_WIDTH * scale) / 2.0;
                     let half_h = (CARD_HEIGHT * scale) / 2.0;
 
-                    let dx = (world_pos.x - transform.translation.x).abs();
-                    let dy = (world_pos.y - transform.translation.y).abs();
+                    let dx = (world_pos.x - target.translation.x).abs();
+                    let dy = (world_pos.y - target.translation.y).abs();
 
                     if dx < half_w && dy < half_h {
-                        let dist = transform.translation.xy().distance(world_pos);
+                        let dist = target.translation.xy().distance(world_pos);
                         if dist < min_dist {
                             if let Some(target_card) = game.get_tableau_top(match card.pile_type {
                                 PileType::Tableau(c) => c,
@@ -168,6 +203,8 @@ fn handle_mouse_input(
                                 if target_card.rank.value() == selected.card.rank.value() + 1 {
                                     min_dist = dist;
                                     target_pile = Some(card.pile_type);
+                                } else {
+                                    rejected_pile = Some(card.pile_type);
                                 }
                             }
                         }
@@ -176,16 +213,16 @@ fn handle_mouse_input(
             }
 
             if target_pile.is_none() {
-                for (transform, empty) in empty_query.iter() {
-                    let scale = transform.scale.x;
+                for (target, empty) in empty_query.iter() {
+                    let scale = target.scale;
                     let half_w = (CARD_WIDTH * scale) / 2.0;
                     let half_h = (CARD_HEIGHT * scale) / 2.0;
 
-                    let dx = (world_pos.x - transform.translation.x).abs();
-                    let dy = (world_pos.y - transform.translation.y).abs();
+                    let dx = (world_pos.x - target.translation.x).abs();
+                    let dy = (world_pos.y - target.translation.y).abs();
 
                     if dx < half_w && dy < half_h {
-                        let dist = transform.translation.xy().distance(world_pos);
+                        let dist = target.translation.xy().distance(world_pos);
                         if dist < min_dist {
                             min_dist = dist;
                             target_pile = Some(empty.pile_type);
@@ -195,50 +232,38 @@ fn handle_mouse_input(
             }
 
             if let Some(to_pile) = target_pile {
-                let mut valid_move = false;
-                if let PileType::Tableau(to_col) = to_pile {
-                    if let Some(top) = game.get_tableau_top(to_col) {
-                        if selected.card.can_stack_on(top) {
-                            valid_move = true;
-                        }
-                    } else {
-                        valid_move = true;
+                let valid_move = match (selected.from, to_pile) {
+                    (PileType::Tableau(from_col), PileType::Tableau(to_col))
+                        if game.can_move(from_col, selected.index, to_col) =>
+                    {
+                        Some((from_col, to_col))
                     }
-                }
+                    _ => None,
+                };
 
-                if valid_move {
-                    let mut snapshot = (*game).clone();
-                    snapshot.selected = None;
-                    snapshot.drag_pos = None;
-                    snapshot.drag_offset = None;
-                    snapshot.auto_move_to = None;
-                    history.stack.push(snapshot);
-
-                    let target_idx = match to_pile {
-                        PileType::Tableau(col) => game.tableau[col].len(),
-                        _ => 0,
-                    };
-
-                    let total_cards_for_drop = match to_pile {
-                        PileType::Tableau(_) => target_idx + 1,
-                        _ => 1,
-                    };
-
-                    let (target_pos_3d, _) =
-                        get_pile_position(to_pile, target_idx, window_size, total_cards_for_drop);
-
-                    game.auto_move_to =
-                        Some((Vec2::new(target_pos_3d.x, target_pos_3d.y), to_pile));
-
-                    if let Some(sounds) = game_sounds {
-                        commands
-                            .spawn((AudioPlayer(sounds.drop.clone()), PlaybackSettings::DESPAWN));
-                    }
+                if let Some((from_col, to_col)) = valid_move {
+                    move_writer.write(MoveEvent {
+                        from_col,
+                        index: selected.index,
+                        to_col,
+                        drag_pos: game.drag_pos,
+                    });
+                    sound_writer.write(PlaySound(SoundCue::Drop));
                 } else {
-                    game.selected = None;
-                    game.drag_pos = None;
+                    invalid_writer.write(InvalidMoveEvent {
+                        card: selected.card,
+                        to: Some(to_pile),
+                    });
                 }
+                game.selected = None;
+                game.drag_pos = None;
             } else {
+                if let Some(to_pile) = rejected_pile {
+                    invalid_writer.write(InvalidMoveEvent {
+                        card: selected.card,
+                        to: Some(to_pile),
+                    });
+                }
                 game.selected = None;
                 game.drag_pos = None;
             }
@@ -261,3 +286,27 @@ fn handle_keyboard_input(
         next_state.set(GameState::Menu);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn press_during_animation_is_replayed_once_idle() {
+        let mut buffered = BufferedPress::default();
+        let press = Vec2::new(10.0, 20.0);
+        assert_eq!(buffered.take(true, true, press), None);
+        assert_eq!(buffered.take(true, false, Vec2::ZERO), None);
+        assert_eq!(buffered.take(false, false, Vec2::ZERO), Some(press));
+        assert_eq!(buffered.take(false, false, Vec2::ZERO), None);
+    }
+
+    #[test]
+    fn latest_press_wins_and_idle_press_is_immediate() {
+        let mut buffered = BufferedPress::default();
+        buffered.take(true, true, Vec2::ONE);
+        buffered.take(true, true, Vec2::X);
+        assert_eq!(buffered.take(false, false, Vec2::ZERO), Some(Vec2::X));
+        assert_eq!(buffered.take(false, true, Vec2::Y), Some(Vec2::Y));
+    }
+}
@@ -1,4 +1,5 @@
-use crate::resources::PileType;
+use crate::models::Card;
+use crate::resources::{PileType, SpiderGame};
 use bevy::prelude::*;
 
 const BASE_CARD_WIDTH: f32 = 120.0;
@@ -7,73 +8,357 @@ pub const BASE_VERTICAL_OFFSET: f32 = 35.0;
 pub const CARD_WIDTH: f32 = BASE_CARD_WIDTH;
 pub const CARD_HEIGHT: f32 = 168.0;
 
+const MAX_GAME_WIDTH: f32 = 3000.0;
+const PORTRAIT_ASPECT: f32 = 1.0;
+const PORTRAIT_MARGIN: f32 = 16.0;
+const PORTRAIT_HUD_HEIGHT: f32 = 100.0;
+const MIN_STACKED_OFFSET: f32 = 6.0;
+const MIN_LOOSE_OFFSET: f32 = 10.0;
+
+#[derive(Clone, Copy)]
+pub struct ColumnShape {
+    pub total: usize,
+    pub hidden: usize,
+    pub run_start: Option<usize>,
+}
+
+impl ColumnShape {
+    pub fn loose(total: usize) -> Self {
+        Self {
+            total,
+            hidden: 0,
+            run_start: None,
+        }
+    }
+
+    pub fn of(column: &[Card], total: usize, collapse: bool) -> Self {
+        let column = &column[..total.min(column.len())];
+        let hidden = column.iter().filter(|c| !c.face_up).count();
+        let run_start = collapse.then(|| {
+            let mut start = column.len();
+            while start > 0 && column[start - 1].face_up {
+                if let Some(below) = column.get(start) {
+                    let above = &column[start - 1];
+                    if below.suit != above.suit || !below.can_stack_on(above) {
+                        break;
+                    }
+                }
+                start -= 1;
+            }
+            start
+        });
+        Self {
+            total,
+            hidden,
+            run_start,
+        }
+    }
+}
+
+pub fn pile_shape(game: &SpiderGame, pile: PileType, total: usize, collapse: bool) -> ColumnShape {
+    match pile {
+        PileType::Tableau(col) => ColumnShape::of(&game.tableau[col], total, collapse),
+        _ => ColumnShape::loose(total),
+    }
+}
+
+struct ColumnOffsets {
+    stacked: usize,
+    stacked_offset: f32,
+    loose_offset: f32,
+}
+
+impl ColumnOffsets {
+    fn y(&self, index: usize) -> f32 {
+        index.min(self.stacked) as f32 * self.stacked_offset
+            + index.saturating_sub(self.stacked) as f32 * self.loose_offset
+    }
+}
+
+pub trait LayoutStrategy {
+    fn pile_position(
+        &self,
+        pile_type: PileType,
+        index: usize,
+        window_size: Vec2,
+        shape: ColumnShape,
+    ) -> (Vec3, f32);
+}
+
+pub struct LandscapeLayout;
+
+pub struct PortraitLayout;
+
+pub fn layout_for(window_size: Vec2) -> &'static dyn LayoutStrategy {
+    if window_size.x < window_size.y * PORTRAIT_ASPECT {
+        &PortraitLayout
+    } else {
+        &LandscapeLayout
+    }
+}
+
 pub fn get_pile_position(
     pile_type: PileType,
     index: usize,
     window_size: Vec2,
-    total_cards: usize,
+    shape: ColumnShape,
 ) -> (Vec3, f32) {
-    let w = window_size.x;
-    let h = window_size.y;
+    layout_for(window_size).pile_position(pile_type, index, window_size, shape)
+}
 
-    let max_game_width = 3000.0;
-    let game_width = w.min(max_game_width) * 0.95;
+fn fit_column(
+    total: usize,
+    stacked: usize,
+    standard_offset: f32,
+    available_height: f32,
+    card_visual_height: f32,
+    scale: f32,
+) -> ColumnOffsets {
+    let gaps = total.saturating_sub(1);
+    let stacked_gaps = stacked.min(gaps);
+    let loose_gaps = gaps - stacked_gaps;
+    let room = available_height - card_visual_height;
 
-    let spacing_x = game_width / 10.0;
+    let mut offsets = ColumnOffsets {
+        stacked,
+        stacked_offset: standard_offset,
+        loose_offset: standard_offset,
+    };
+    if gaps as f32 * standard_offset <= room {
+        return offsets;
+    }
+    if stacked_gaps > 0 {
+        offsets.stacked_offset = ((room - loose_gaps as f32 * standard_offset)
+            / stacked_gaps as f32)
+            .clamp(MIN_STACKED_OFFSET * scale, standard_offset);
+    }
+    if loose_gaps > 0 {
+        let loose_room = room - stacked_gaps as f32 * offsets.stacked_offset;
+        offsets.loose_offset =
+            (loose_room / loose_gaps as f32).clamp(MIN_LOOSE_OFFSET * scale, standard_offset);
+    }
+    offsets
+}
 
-    let target_card_width = spacing_x * 0.90;
+fn column_offsets(
+    shape: ColumnShape,
+    standard_offset: f32,
+    available_height: f32,
+    card_visual_height: f32,
+    scale: f32,
+) -> ColumnOffsets {
+    let fit = |stacked| {
+        fit_column(
+            shape.total,
+            stacked,
+            standard_offset,
+            available_height,
+            card_visual_height,
+            scale,
+        )
+    };
+    let offsets = fit(shape.hidden);
+    match shape.run_start {
+        Some(run_start) if run_start > shape.hidden && offsets.loose_offset < standard_offset => {
+            fit(run_start)
+        }
+        _ => offsets,
+    }
+}
 
-    let scale_w = target_card_width / BASE_CARD_WIDTH;
-    let scale_h = h / 900.0;
+impl LayoutStrategy for LandscapeLayout {
+    fn pile_position(
+        &self,
+        pile_type: PileType,
+        index: usize,
+        window_size: Vec2,
+        shape: ColumnShape,
+    ) -> (Vec3, f32) {
+        let w = window_size.x;
+        let h = window_size.y;
 
-    let scale = scale_w.min(scale_h).clamp(0.4, 2.5);
+        let game_width = w.min(MAX_GAME_WIDTH) * 0.95;
 
-    let standard_offset = BASE_VERTICAL_OFFSET * scale;
-    let tableau_top_y = h / 2.0 - (100.0 * scale).max(60.0);
+        let spacing_x = game_width / 10.0;
 
-    let mut current_offset = standard_offset;
+        let target_card_width = spacing_x * 0.90;
 
-    if let PileType::Tableau(_) = pile_type {
-        if total_cards > 1 {
-            let card_visual_height = CARD_HEIGHT * scale;
+        let scale_w = target_card_width / BASE_CARD_WIDTH;
+        let scale_h = h / 900.0;
 
-            let bottom_margin = card_visual_height;
+        let scale = scale_w.min(scale_h).clamp(0.4, 2.5);
 
-            let bottom_y = -h / 2.0 + bottom_margin;
+        let standard_offset = BASE_VERTICAL_OFFSET * scale;
+        let tableau_top_y = h / 2.0 - (100.0 * scale).max(60.0);
 
-            let available_height = tableau_top_y - bottom_y;
+        let card_visual_height = CARD_HEIGHT * scale;
+        let bottom_y = -h / 2.0 + card_visual_height;
+        let offsets = column_offsets(
+            shape,
+            standard_offset,
+            tableau_top_y - bottom_y,
+            card_visual_height,
+            scale,
+        );
 
-            let needed_height = (total_cards as f32 - 1.0) * standard_offset + card_visual_height;
+        let start_x = -(9.0 * spacing_x) / 2.0;
 
-            if needed_height > available_height {
-                let compressed =
-                    (available_height - card_visual_height) / (total_cards as f32 - 1.0);
-                current_offset = compressed.max(10.0 * scale);
+        let pos = match pile_type {
+            PileType::Tableau(col) => {
+                let x = start_x + (col as f32 * spacing_x);
+                let y = tableau_top_y - offsets.y(index);
+                Vec3::new(x, y, index as f32 + 1.0)
             }
-        }
+            PileType::Stock => {
+                let x = start_x + (9.0 * spacing_x);
+                let y = -h / 2.0 + (CARD_HEIGHT * scale / 1.5) + 20.0;
+                Vec3::new(x, y, 0.0)
+            }
+            PileType::Foundation(i) => {
+                let base_foundation_x = start_x;
+                let offset_x = i as f32 * (spacing_x * 0.2);
+                let x = base_foundation_x + offset_x;
+                let y = -h / 2.0 + (CARD_HEIGHT * scale / 1.5) + 20.0;
+                Vec3::new(x, y, i as f32)
+            }
+        };
+
+        (pos, scale)
+    }
+}
+
+impl LayoutStrategy for PortraitLayout {
+    fn pile_position(
+        &self,
+        pile_type: PileType,
+        index: usize,
+        window_size: Vec2,
+        shape: ColumnShape,
+    ) -> (Vec3, f32) {
+        let w = window_size.x;
+        let h = window_size.y;
+
+        let game_width = w.min(MAX_GAME_WIDTH) * 0.98;
+        let spacing_x = game_width / 10.0;
+
+        let scale_w = spacing_x * 0.95 / BASE_CARD_WIDTH;
+        let scale_h = h / 1300.0;
+        let scale = scale_w.min(scale_h).clamp(0.3, 2.5);
+
+        let card_visual_height = CARD_HEIGHT * scale;
+        let top_row_y = h / 2.0 - PORTRAIT_MARGIN - card_visual_height / 2.0;
+        let tableau_top_y = top_row_y - card_visual_height - PORTRAIT_MARGIN;
+        let bottom_y = -h / 2.0 + PORTRAIT_HUD_HEIGHT + card_visual_height / 2.0;
+
+        let standard_offset = BASE_VERTICAL_OFFSET * scale * 1.25;
+        let offsets = column_offsets(
+            shape,
+            standard_offset,
+            tableau_top_y - bottom_y + card_visual_height,
+            card_visual_height,
+            scale,
+        );
+
+        let start_x = -(9.0 * spacing_x) / 2.0;
+
+        let pos = match pile_type {
+            PileType::Tableau(col) => {
+                let x = start_x + (col as f32 * spacing_x);
+                let y = tableau_top_y - offsets.y(index);
+                Vec3::new(x, y, index as f32 + 1.0)
+            }
+            PileType::Stock => Vec3::new(start_x + 9.0 * spacing_x, top_row_y, 0.0),
+            PileType::Foundation(i) => {
+                let x = start_x + i as f32 * (spacing_x * 0.2);
+                Vec3::new(x, top_row_y, i as f32)
+            }
+        };
+
+        (pos, scale)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Rank, Suit};
 
-    let start_x = -(9.0 * spacing_x) / 2.0;
+    const OFFSET: f32 = 35.0;
+    const CARD: f32 = 168.0;
 
-    let pos = match pile_type {
-        PileType::Tableau(col) => {
-            let x = start_x + (col as f32 * spacing_x);
-            let y = tableau_top_y - (index as f32 * current_offset);
-            Vec3::new(x, y, index as f32 + 1.0)
+    fn card(rank: Rank, suit: Suit, face_up: bool) -> Card {
+        Card {
+            suit,
+            rank,
+            face_up,
         }
-        PileType::Stock => {
-            let x = start_x + (9.0 * spacing_x);
-            let y = -h / 2.0 + (CARD_HEIGHT * scale / 1.5) + 20.0;
-            Vec3::new(x, y, 0.0)
+    }
+
+    fn shape(total: usize, hidden: usize, run_start: Option<usize>) -> ColumnShape {
+        ColumnShape {
+            total,
+            hidden,
+            run_start,
         }
-        PileType::Foundation(i) => {
-            let base_foundation_x = start_x;
-            let offset_x = i as f32 * (spacing_x * 0.2);
-            let x = base_foundation_x + offset_x;
-            let y = -h / 2.0 + (CARD_HEIGHT * scale / 1.5) + 20.0;
-            Vec3::new(x, y, i as f32)
+    }
+
+    #[test]
+    fn short_column_keeps_standard_spacing() {
+        let offsets = fit_column(6, 5, OFFSET, 1000.0, CARD, 1.0);
+        assert_eq!(offsets.stacked_offset, OFFSET);
+        assert_eq!(offsets.loose_offset, OFFSET);
+    }
+
+    #[test]
+    fn face_down_cards_are_squeezed_first() {
+        let room = 14.0 * OFFSET + 5.0 * 10.0;
+        let offsets = fit_column(20, 5, OFFSET, room + CARD, CARD, 1.0);
+        assert_eq!(offsets.stacked_offset, 10.0);
+        assert_eq!(offsets.loose_offset, OFFSET);
+        assert_eq!(offsets.y(19), room);
+    }
+
+    #[test]
+    fn collapse_keeps_the_run_at_full_spacing() {
+        let room = 10.0 * 8.0 + 5.0 * OFFSET;
+        let available = room + CARD;
+
+        let loose = column_offsets(shape(16, 2, None), OFFSET, available, CARD, 1.0);
+        assert!(loose.loose_offset < OFFSET);
+
+        let collapsed = column_offsets(shape(16, 2, Some(10)), OFFSET, available, CARD, 1.0);
+        assert_eq!(collapsed.stacked, 10);
+        assert_eq!(collapsed.stacked_offset, 8.0);
+        assert_eq!(collapsed.loose_offset, OFFSET);
+        assert_eq!(collapsed.y(15), room);
+    }
+
+    #[test]
+    fn offsets_never_drop_below_the_floors() {
+        for scale in [1.0, 0.5] {
+            let offsets = fit_column(30, 10, OFFSET * scale, 200.0, CARD * scale, scale);
+            assert_eq!(offsets.stacked_offset, MIN_STACKED_OFFSET * scale);
+            assert_eq!(offsets.loose_offset, MIN_LOOSE_OFFSET * scale);
         }
-    };
+    }
+
+    #[test]
+    fn shape_only_counts_cards_up_to_total() {
+        let column = [
+            card(Rank::King, Suit::Spades, false),
+            card(Rank::Queen, Suit::Spades, false),
+            card(Rank::Nine, Suit::Spades, true),
+            card(Rank::Eight, Suit::Spades, true),
+            card(Rank::Seven, Suit::Hearts, true),
+        ];
+        let full = ColumnShape::of(&column, 5, true);
+        assert_eq!((full.hidden, full.run_start), (2, Some(4)));
 
-    (pos, scale)
+        let partial = ColumnShape::of(&column, 3, true);
+        assert_eq!((partial.hidden, partial.run_start), (2, Some(2)));
+
+        let hidden_only = ColumnShape::of(&column, 1, true);
+        assert_eq!((hidden_only.hidden, hidden_only.run_start), (1, Some(1)));
+        assert_eq!(ColumnShape::of(&column, 3, false).run_start, None);
+    }
 }
@@ -0,0 +1,70 @@
+pub mod achievements;
+pub mod animation;
+pub mod announce;
+pub mod atlas;
+pub mod card_render;
+pub mod celebration;
+pub mod daily;
+pub mod debug;
+pub mod input;
+pub mod layout;
+pub mod loading;
+pub mod models;
+pub mod music;
+pub mod options;
+pub mod resources;
+pub mod scoring;
+pub mod settings;
+pub mod solver;
+pub mod sound;
+pub mod stats;
+pub mod storage;
+pub mod summary;
+pub mod systems;
+pub mod theme;
+pub mod ui;
+pub mod windowing;
+pub mod winnable;
+
+use bevy::prelude::*;
+use resources::{
+    AnimationEventQueue, GameAssets, GameHistory, GameState, SpiderGame, StartAnimationEvent,
+    WarmUpState,
+};
+use systems::*;
+
+pub struct SpiderPlugin;
+
+impl Plugin for SpiderPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<GameState>()
+            .add_message::<DealEvent>()
+            .add_message::<DealStockEvent>()
+            .add_message::<StartAnimationEvent>()
+            .add_message::<AnimationFinishedEvent>()
+            .add_message::<UndoEvent>()
+            .add_message::<MoveEvent>()
+            .add_message::<MoveAppliedEvent>()
+            .add_message::<SuitCompletedEvent>()
+            .add_message::<InvalidMoveEvent>()
+            .insert_resource(SpiderGame::default())
+            .insert_resource(AnimationEventQueue::default())
+            .insert_resource(GameHistory::default())
+            .insert_resource(GameAssets::default())
+            .insert_resource(WarmUpState { frames: 0 })
+            .add_systems(
+                Update,
+                (
+                    animation_event_dispatcher.run_if(in_state(GameState::Playing)),
+                    deal_system,
+                    deal_stock_system,
+                    move_cards_system
+                        .after(animation_event_dispatcher)
+                        .after(deal_stock_system),
+                    undo_system,
+                    game_clock_system.run_if(in_state(GameState::Playing)),
+                    game_over_system.run_if(in_state(GameState::Playing)),
+                ),
+            );
+    }
+}
@@ -0,0 +1,290 @@
+use std::collections::HashSet;
+
+use bevy::asset::{LoadState, UntypedAssetId};
+use bevy::prelude::*;
+
+use crate::music::MusicPlaylist;
+use crate::resources::{GameAssets, GameState, WarmUpState};
+use crate::sound::GameSounds;
+use crate::theme::apply_theme;
+
+const LISTED_FAILURES: usize = 10;
+const BAR_WIDTH: f32 = 500.0;
+
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LoadProgress>()
+            .add_systems(OnEnter(GameState::Loading), setup_loading_screen)
+            .add_systems(
+                Update,
+                (
+                    check_assets_ready.after(apply_theme),
+                    update_loading_screen,
+                    handle_continue,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Loading)),
+            )
+            .add_systems(OnExit(GameState::Loading), cleanup_loading_screen);
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct LoadProgress {
+    pub total: usize,
+    pub settled: usize,
+    pub failed: Vec<(UntypedAssetId, String)>,
+    continued: bool,
+}
+
+impl LoadProgress {
+    pub fn has_failed(&self, id: impl Into<UntypedAssetId>) -> bool {
+        let id = id.into();
+        self.failed.iter().any(|(failed, _)| *failed == id)
+    }
+}
+
+#[derive(Component)]
+struct LoadingEntity;
+
+#[derive(Component)]
+struct ProgressFill;
+
+#[derive(Component)]
+struct ProgressText;
+
+#[derive(Component)]
+struct FailurePanel;
+
+#[derive(Component)]
+struct ContinueButton;
+
+fn required_assets(
+    game_assets: &GameAssets,
+    game_sounds: &GameSounds,
+    playlist: &MusicPlaylist,
+) -> Vec<UntypedHandle> {
+    let mut seen = HashSet::new();
+    game_assets
+        .faces
+        .values()
+        .chain([&game_assets.back_texture, &game_assets.blank_texture])
+        .map(|handle| handle.clone().untyped())
+        .chain([game_assets.font.clone().untyped()])
+        .chain(
+            game_sounds
+                .cues
+                .values()
+                .map(|source| source.clone().untyped()),
+        )
+        .chain(playlist.tracks.iter().map(|track| track.clone().untyped()))
+        .filter(|handle| seen.insert(handle.id()))
+        .collect()
+}
+
+fn check_assets_ready(
+    mut next_state: ResMut<NextState<GameState>>,
+    asset_server: Res<AssetServer>,
+    game_assets: Res<GameAssets>,
+    game_sounds: Res<GameSounds>,
+    playlist: Res<MusicPlaylist>,
+    mut progress: ResMut<LoadProgress>,
+    mut warm_up: ResMut<WarmUpState>,
+) {
+    if game_assets.faces.is_empty() {
+        return;
+    }
+    let required = required_assets(&game_assets, &game_sounds, &playlist);
+    let mut settled = 0;
+    for handle in &required {
+        let Some(path) = handle.path() else {
+            settled += 1;
+            continue;
+        };
+        match asset_server.get_load_state(handle.id()) {
+            Some(LoadState::Loaded) => settled += 1,
+            Some(LoadState::Failed(err)) => {
+                settled += 1;
+                if !progress.has_failed(handle.id()) {
+                    warn!("Failed to load {}: {}", path, err);
+                    progress.failed.push((handle.id(), path.to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+    if progress.total != required.len() || progress.settled != settled {
+        progress.total = required.len();
+        progress.settled = settled;
+    }
+
+    let all_ready = settled == required.len() && (progress.failed.is_empty() || progress.continued);
+    if all_ready {
+        if warm_up.frames == 0 {
+            warm_up.frames = 1;
+        } else if warm_up.frames < 10 {
+            warm_up.frames += 1;
+        } else {
+            next_state.set(GameState::Menu);
+        }
+    }
+}
+
+fn loading_text(text: impl Into<String>, font_size: f32) -> (Text, TextFont, TextColor) {
+    (
+        Text::new(text),
+        TextFont {
+            font_size,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+    )
+}
+
+fn setup_loading_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            LoadingEntity,
+        ))
+        .with_children(|parent| {
+            parent.spawn(loading_text("Loading", 50.0));
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(BAR_WIDTH),
+                        height: Val::Px(24.0),
+                        padding: UiRect::all(Val::Px(3.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.4)),
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        Node {
+                            width: Val::Percent(0.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::WHITE),
+                        ProgressFill,
+                    ));
+                });
+            parent.spawn((loading_text("", 20.0), ProgressText));
+            parent.spawn((
+                Node {
+                    display: Display::None,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(6.0),
+                    padding: UiRect::all(Val::Px(16.0)),
+                    max_width: Val::Px(BAR_WIDTH * 1.5),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+                FailurePanel,
+            ));
+        });
+}
+
+#[allow(clippy::type_complexity)]
+fn update_loading_screen(
+    mut commands: Commands,
+    progress: Res<LoadProgress>,
+    mut fill: Query<&mut Node, (With<ProgressFill>, Without<FailurePanel>)>,
+    mut text: Query<&mut Text, With<ProgressText>>,
+    mut panel: Query<(Entity, &mut Node), (With<FailurePanel>, Without<ProgressFill>)>,
+    mut shown_failures: Local<usize>,
+) {
+    if !progress.is_changed() || progress.total == 0 {
+        return;
+    }
+    if let Ok(mut node) = fill.single_mut() {
+        node.width = Val::Percent(100.0 * progress.settled as f32 / progress.total as f32);
+    }
+    if let Ok(mut text) = text.single_mut() {
+        **text = format!("{} / {} files", progress.settled, progress.total);
+    }
+
+    let waiting = progress.settled < progress.total;
+    if waiting || progress.failed.is_empty() || *shown_failures == progress.failed.len() {
+        return;
+    }
+    let Ok((panel, mut node)) = panel.single_mut() else {
+        return;
+    };
+    *shown_failures = progress.failed.len();
+    node.display = Display::Flex;
+    commands.entity(panel).despawn_children();
+    commands.entity(panel).with_children(|panel| {
+        panel.spawn(loading_text("Some files could not be loaded:", 24.0));
+        for (_, path) in progress.failed.iter().take(LISTED_FAILURES) {
+            panel.spawn(loading_text(path.clone(), 16.0));
+        }
+        if progress.failed.len() > LISTED_FAILURES {
+            panel.spawn(loading_text(
+                format!("and {} more", progress.failed.len() - LISTED_FAILURES),
+                16.0,
+            ));
+        }
+        panel.spawn(loading_text(
+            "Missing card art will be drawn instead and missing sounds stay silent.",
+            16.0,
+        ));
+        panel
+            .spawn((
+                Button,
+                Node {
+                    width: Val::Px(250.0),
+                    height: Val::Px(50.0),
+                    margin: UiRect::top(Val::Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.4, 0.4, 0.4)),
+                ContinueButton,
+            ))
+            .with_children(|button| {
+                button.spawn(loading_text("Continue", 25.0));
+            });
+    });
+}
+
+fn handle_continue(
+    mut progress: ResMut<LoadProgress>,
+    mut game_assets: ResMut<GameAssets>,
+    mut game_sounds: ResMut<GameSounds>,
+    mut playlist: ResMut<MusicPlaylist>,
+    button_query: Query<&Interaction, (Changed<Interaction>, With<ContinueButton>)>,
+) {
+    if !button_query.iter().any(|i| *i == Interaction::Pressed) {
+        return;
+    }
+    if progress.has_failed(game_assets.font.id()) {
+        game_assets.font = Handle::default();
+    }
+    game_sounds
+        .cues
+        .retain(|_, source| !progress.has_failed(source.id()));
+    playlist
+        .tracks
+        .retain(|track| !progress.has_failed(track.id()));
+    progress.continued = true;
+}
+
+fn cleanup_loading_screen(mut commands: Commands, query: Query<Entity, With<LoadingEntity>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -1,5 +1,8 @@
 use crate::resources::Difficulty;
 use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Suit {
@@ -10,11 +13,23 @@ pub enum Suit {
 }
 
 impl Suit {
-    #[allow(dead_code)]
+    pub fn all() -> [Suit; 4] {
+        [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades]
+    }
+
     pub fn is_red(&self) -> bool {
         matches!(self, Suit::Hearts | Suit::Diamonds)
     }
 
+    pub fn name(&self) -> &str {
+        match self {
+            Suit::Hearts => "hearts",
+            Suit::Diamonds => "diamonds",
+            Suit::Clubs => "clubs",
+            Suit::Spades => "spades",
+        }
+    }
+
     pub fn as_str(&self) -> &str {
         match self {
             Suit::Hearts => "H",
@@ -61,6 +76,16 @@ impl Rank {
         }
     }
 
+    pub fn name(&self) -> &str {
+        match self {
+            Rank::Ace => "ace",
+            Rank::Jack => "jack",
+            Rank::Queen => "queen",
+            Rank::King => "king",
+            _ => self.as_str(),
+        }
+    }
+
     pub fn value(&self) -> u8 {
         match self {
             Rank::Ace => 1,
@@ -114,6 +139,10 @@ impl Card {
         }
     }
 
+    pub fn name(&self) -> String {
+        format!("{} of {}", self.rank.name(), self.suit.name())
+    }
+
     pub fn new_spider_deck(difficulty: Difficulty) -> Vec<Card> {
         let mut deck = Vec::new();
         match difficulty {
@@ -141,10 +170,15 @@ impl Card {
         deck
     }
 
+    pub fn shuffled_spider_deck(difficulty: Difficulty, seed: u64) -> Vec<Card> {
+        let mut deck = Self::new_spider_deck(difficulty);
+        deck.shuffle(&mut ChaCha8Rng::seed_from_u64(seed));
+        deck
+    }
+
     pub fn new_standard_deck() -> Vec<Card> {
-        let suits = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades];
         let mut deck = Vec::new();
-        for suit in suits {
+        for suit in Suit::all() {
             for rank in Rank::all() {
                 deck.push(Card::new(suit, rank));
             }
@@ -156,11 +190,9 @@ impl Card {
         self.rank.value() + 1 == other.rank.value()
     }
 
-    pub fn texture_path(&self) -> String {
-        if self.face_up {
-            format!("cards/{}{}.png", self.rank.as_str(), self.suit.as_str())
-        } else {
-            "deck_black.png".to_string()
-        }
+    pub fn face_path(&self, pattern: &str) -> String {
+        pattern
+            .replace("{rank}", self.rank.as_str())
+            .replace("{suit}", self.suit.as_str())
     }
 }
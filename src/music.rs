@@ -0,0 +1,169 @@
+use bevy::asset::io::file::FileAssetReader;
+use bevy::audio::Volume;
+use bevy::prelude::*;
+
+use crate::resources::GameState;
+use crate::settings::Settings;
+
+const MUSIC_DIR: &str = "music";
+const CROSSFADE_SECS: f32 = 4.0;
+const DUCK_LEVEL: f32 = 0.3;
+const DUCK_SPEED: f32 = 2.0;
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_playlist)
+            .add_systems(Update, (advance_playlist, fade_music).chain());
+    }
+}
+
+#[derive(Resource)]
+pub struct MusicPlaylist {
+    pub tracks: Vec<Handle<AudioSource>>,
+    next: usize,
+}
+
+#[derive(Component)]
+struct MusicTrack {
+    gain: f32,
+    fading_out: bool,
+    duration: Option<f32>,
+}
+
+fn load_playlist(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let dir = FileAssetReader::get_base_path()
+        .join("assets")
+        .join(MUSIC_DIR);
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name.to_lowercase().ends_with(".ogg"))
+        .collect();
+    names.sort();
+    let tracks = names
+        .iter()
+        .map(|name| asset_server.load(format!("{}/{}", MUSIC_DIR, name)))
+        .collect();
+    commands.insert_resource(MusicPlaylist { tracks, next: 0 });
+}
+
+fn ogg_duration(bytes: &[u8]) -> Option<f32> {
+    let header = bytes
+        .windows(7)
+        .take(512)
+        .position(|w| w == b"\x01vorbis")?;
+    let rate = u32::from_le_bytes(bytes.get(header + 12..header + 16)?.try_into().ok()?);
+    let last_page = bytes.windows(4).rposition(|w| w == b"OggS")?;
+    let granule = i64::from_le_bytes(bytes.get(last_page + 6..last_page + 14)?.try_into().ok()?);
+    (rate > 0 && granule > 0).then(|| granule as f32 / rate as f32)
+}
+
+pub fn music_volume(settings: &Settings) -> f32 {
+    if settings.muted {
+        0.0
+    } else {
+        settings.master_volume * settings.music_volume
+    }
+}
+
+fn music_plays(state: &GameState) -> bool {
+    matches!(state, GameState::Playing | GameState::Won | GameState::Lost)
+}
+
+fn advance_playlist(
+    mut commands: Commands,
+    mut playlist: ResMut<MusicPlaylist>,
+    sources: Res<Assets<AudioSource>>,
+    settings: Res<Settings>,
+    state: Res<State<GameState>>,
+    mut tracks: Query<(Entity, &mut MusicTrack, Option<&AudioSink>)>,
+) {
+    let mut playing = false;
+    for (entity, mut track, sink) in tracks.iter_mut() {
+        let Some(sink) = sink else {
+            playing |= !track.fading_out;
+            continue;
+        };
+        if sink.empty() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        if track.fading_out {
+            continue;
+        }
+        let near_end = track
+            .duration
+            .is_some_and(|duration| sink.position().as_secs_f32() >= duration - CROSSFADE_SECS);
+        if near_end {
+            track.fading_out = true;
+        } else {
+            playing = true;
+        }
+    }
+
+    if playing
+        || playlist.tracks.is_empty()
+        || music_volume(&settings) <= 0.0
+        || !music_plays(state.get())
+    {
+        return;
+    }
+    let handle = playlist.tracks[playlist.next % playlist.tracks.len()].clone();
+    let Some(source) = sources.get(&handle) else {
+        return;
+    };
+    playlist.next = (playlist.next + 1) % playlist.tracks.len();
+    commands.spawn((
+        AudioPlayer(handle),
+        PlaybackSettings::ONCE.with_volume(Volume::Linear(0.0)),
+        MusicTrack {
+            gain: 0.0,
+            fading_out: false,
+            duration: ogg_duration(&source.bytes),
+        },
+    ));
+}
+
+fn fade_music(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    state: Res<State<GameState>>,
+    mut tracks: Query<(Entity, &mut MusicTrack, &mut AudioSink)>,
+    mut duck: Local<Option<f32>>,
+) {
+    let dt = time.delta_secs();
+    let duck_target = if *state.get() == GameState::Won {
+        DUCK_LEVEL
+    } else {
+        1.0
+    };
+    let current = duck.get_or_insert(1.0);
+    *current += (duck_target - *current).clamp(-DUCK_SPEED * dt, DUCK_SPEED * dt);
+    let level = music_volume(&settings) * *current;
+    let paused = !music_plays(state.get());
+
+    for (entity, mut track, mut sink) in tracks.iter_mut() {
+        if paused {
+            if !sink.is_paused() {
+                sink.pause();
+            }
+            continue;
+        }
+        if sink.is_paused() {
+            sink.play();
+        }
+        let target = if track.fading_out { 0.0 } else { 1.0 };
+        let step = dt / CROSSFADE_SECS;
+        track.gain += (target - track.gain).clamp(-step, step);
+        if track.fading_out && track.gain <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        sink.set_volume(Volume::Linear(track.gain * level));
+    }
+}
@@ -0,0 +1,280 @@
+use bevy::prelude::*;
+use bevy::window::Monitor;
+
+use crate::resources::{GameAssets, GameState};
+use crate::settings::Settings;
+use crate::theme::{ThemeBackground, Themes};
+use crate::windowing::WindowPrefs;
+
+pub struct OptionsPlugin;
+
+impl Plugin for OptionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Options), setup_options_screen)
+            .add_systems(
+                Update,
+                (handle_options_interaction, refresh_option_labels)
+                    .chain()
+                    .run_if(in_state(GameState::Options)),
+            )
+            .add_systems(OnExit(GameState::Options), cleanup_options_screen);
+    }
+}
+
+#[derive(Component)]
+struct OptionsEntity;
+
+#[derive(Component, Clone, Copy)]
+enum OptionButton {
+    Theme,
+    CardArt,
+    FourColour,
+    HighContrast,
+    Patterns,
+    MoveLog,
+    LongColumns,
+    Display,
+    Monitor,
+    MasterVolume,
+    EffectsVolume,
+    MusicVolume,
+    Mute,
+    AnimationSpeed,
+    Easing,
+    Back,
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "On"
+    } else {
+        "Off"
+    }
+}
+
+fn next_volume(volume: f32) -> f32 {
+    let step = (volume * 10.0).round() as i32 + 1;
+    if step > 10 {
+        0.0
+    } else {
+        step as f32 / 10.0
+    }
+}
+
+fn percent(volume: f32) -> String {
+    format!("{}%", (volume * 100.0).round() as i32)
+}
+
+fn option_label(
+    button: OptionButton,
+    settings: &Settings,
+    themes: &Themes,
+    window: &WindowPrefs,
+) -> String {
+    match button {
+        OptionButton::Theme => format!("Theme: {}", themes.get(&settings.theme).manifest.name),
+        OptionButton::CardArt if settings.procedural_cards => "Card Art: Drawn".to_string(),
+        OptionButton::CardArt if settings.drawn_cards() => "Card Art: Drawn (Access)".to_string(),
+        OptionButton::CardArt => "Card Art: Theme".to_string(),
+        OptionButton::FourColour => {
+            format!("Four-Colour Deck: {}", on_off(settings.four_colour_deck))
+        }
+        OptionButton::HighContrast => format!("High Contrast: {}", on_off(settings.high_contrast)),
+        OptionButton::Patterns => format!("Card Patterns: {}", on_off(settings.card_patterns)),
+        OptionButton::MoveLog => format!("Move Log: {}", on_off(settings.move_log)),
+        OptionButton::LongColumns if settings.collapse_long_columns => {
+            "Long Columns: Collapse".to_string()
+        }
+        OptionButton::LongColumns => "Long Columns: Squeeze".to_string(),
+        OptionButton::Display if window.fullscreen => "Display: Fullscreen".to_string(),
+        OptionButton::Display => "Display: Windowed".to_string(),
+        OptionButton::Monitor => window.monitor_label(),
+        OptionButton::MasterVolume => format!("Master Volume: {}", percent(settings.master_volume)),
+        OptionButton::EffectsVolume => {
+            format!("Effects Volume: {}", percent(settings.effects_volume))
+        }
+        OptionButton::MusicVolume => format!("Music Volume: {}", percent(settings.music_volume)),
+        OptionButton::Mute => format!("Mute: {}", on_off(settings.muted)),
+        OptionButton::AnimationSpeed => {
+            format!("Animations: {}", settings.animation_speed.label())
+        }
+        OptionButton::Easing => format!("Easing: {}", settings.easing.label()),
+        OptionButton::Back => "Back".to_string(),
+    }
+}
+
+fn spawn_option_button(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    button: OptionButton,
+    color: Color,
+    label: String,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(400.0),
+                height: Val::Px(50.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(color),
+            button,
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new(label),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 25.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn setup_options_screen(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    settings: Res<Settings>,
+    themes: Res<Themes>,
+    window: Res<WindowPrefs>,
+) {
+    let font = game_assets.font.clone();
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(game_assets.background),
+            ThemeBackground,
+            OptionsEntity,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Options"),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 60.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            let option_color = Color::srgba(0.0, 0.0, 0.0, 0.3);
+            parent
+                .spawn(Node {
+                    display: Display::Grid,
+                    grid_template_columns: RepeatedGridTrack::px(3, 400.0),
+                    column_gap: Val::Px(20.0),
+                    row_gap: Val::Px(12.0),
+                    ..default()
+                })
+                .with_children(|grid| {
+                    for button in [
+                        OptionButton::Theme,
+                        OptionButton::CardArt,
+                        OptionButton::FourColour,
+                        OptionButton::HighContrast,
+                        OptionButton::Patterns,
+                        OptionButton::MoveLog,
+                        OptionButton::LongColumns,
+                        OptionButton::Display,
+                        OptionButton::Monitor,
+                        OptionButton::MasterVolume,
+                        OptionButton::EffectsVolume,
+                        OptionButton::MusicVolume,
+                        OptionButton::Mute,
+                        OptionButton::AnimationSpeed,
+                        OptionButton::Easing,
+                    ] {
+                        let label = option_label(button, &settings, &themes, &window);
+                        spawn_option_button(grid, &font, button, option_color, label);
+                    }
+                });
+            spawn_option_button(
+                parent,
+                &font,
+                OptionButton::Back,
+                Color::srgb(0.4, 0.4, 0.4),
+                "Back".to_string(),
+            );
+        });
+}
+
+fn handle_options_interaction(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut settings: ResMut<Settings>,
+    mut window: ResMut<WindowPrefs>,
+    themes: Res<Themes>,
+    monitors: Query<&Monitor>,
+    button_query: Query<(&Interaction, &OptionButton), Changed<Interaction>>,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *button {
+            OptionButton::Theme => settings.theme = themes.next(&settings.theme),
+            OptionButton::CardArt => settings.procedural_cards = !settings.procedural_cards,
+            OptionButton::FourColour => settings.four_colour_deck = !settings.four_colour_deck,
+            OptionButton::HighContrast => settings.high_contrast = !settings.high_contrast,
+            OptionButton::Patterns => settings.card_patterns = !settings.card_patterns,
+            OptionButton::MoveLog => settings.move_log = !settings.move_log,
+            OptionButton::LongColumns => {
+                settings.collapse_long_columns = !settings.collapse_long_columns
+            }
+            OptionButton::Display => window.fullscreen = !window.fullscreen,
+            OptionButton::Monitor => window.monitor = window.next_monitor(monitors.iter().count()),
+            OptionButton::MasterVolume => {
+                settings.master_volume = next_volume(settings.master_volume)
+            }
+            OptionButton::EffectsVolume => {
+                settings.effects_volume = next_volume(settings.effects_volume)
+            }
+            OptionButton::MusicVolume => settings.music_volume = next_volume(settings.music_volume),
+            OptionButton::Mute => settings.muted = !settings.muted,
+            OptionButton::AnimationSpeed => {
+                settings.animation_speed = settings.animation_speed.next()
+            }
+            OptionButton::Easing => settings.easing = settings.easing.next(),
+            OptionButton::Back => next_state.set(GameState::Menu),
+        }
+    }
+}
+
+fn refresh_option_labels(
+    settings: Res<Settings>,
+    themes: Res<Themes>,
+    window: Res<WindowPrefs>,
+    button_query: Query<(&OptionButton, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    if !settings.is_changed() && !window.is_changed() {
+        return;
+    }
+    for (button, children) in button_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                let label = option_label(*button, &settings, &themes, &window);
+                if **text != label {
+                    **text = label;
+                }
+            }
+        }
+    }
+}
+
+fn cleanup_options_screen(mut commands: Commands, query: Query<Entity, With<OptionsEntity>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
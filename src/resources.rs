@@ -1,11 +1,19 @@
-use crate::models::Card;
+use crate::models::{Card, Rank, Suit};
+use crate::scoring::ScoringScheme;
 use bevy::prelude::*;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
 pub enum GameState {
     #[default]
     Loading,
     Menu,
+    DailyCalendar,
+    Statistics,
+    Achievements,
+    Options,
     Playing,
     Won,
     Lost,
@@ -18,14 +26,14 @@ pub enum PileType {
     Foundation(usize),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SelectedCard {
     pub card: Card,
     pub from: PileType,
     pub index: usize,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum Difficulty {
     #[default]
     Easy = 1,
@@ -33,6 +41,28 @@ pub enum Difficulty {
     Hard = 4,
 }
 
+impl Difficulty {
+    pub fn all() -> [Difficulty; 3] {
+        [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard]
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            Difficulty::Easy => "One Suit",
+            Difficulty::Medium => "Two Suits",
+            Difficulty::Hard => "Four Suits",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GameMode {
+    #[default]
+    Classic,
+    Daily(NaiveDate),
+    Seeded(u64),
+}
+
 #[derive(Clone, Debug, Message)]
 pub struct StartAnimationEvent {
     pub cards: Vec<Card>,
@@ -41,13 +71,39 @@ pub struct StartAnimationEvent {
     pub from_index: usize,
     pub visual_start_pos: Option<Vec2>,
     pub delay: f32,
-    pub flip_final: bool,
     pub original_pile_len: Option<usize>,
     pub target_index_override: Option<usize>,
     pub fly_z_override: Option<f32>,
 }
 
-#[derive(Resource, Clone)]
+impl StartAnimationEvent {
+    pub fn landing(&self) -> Landing {
+        Landing {
+            pile: self.to,
+            index: self.target_index_override.unwrap_or(0),
+            count: self.cards.len(),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Landing {
+    pub pile: PileType,
+    pub index: usize,
+    pub count: usize,
+}
+
+impl Landing {
+    pub fn covers(&self, pile: PileType, index: usize) -> bool {
+        self.pile == pile
+            && match pile {
+                PileType::Foundation(_) => true,
+                _ => (self.index..self.index + self.count).contains(&index),
+            }
+    }
+}
+
+#[derive(Resource, Debug, Clone, PartialEq)]
 pub struct SpiderGame {
     pub tableau: [Vec<Card>; 10],
     pub stock: Vec<Card>,
@@ -56,10 +112,13 @@ pub struct SpiderGame {
     pub drag_pos: Option<Vec2>,
     pub drag_offset: Option<Vec2>,
     pub move_count: u32,
+    pub undo_count: u32,
     pub score: i32,
-    pub auto_move_to: Option<(Vec2, PileType)>,
     pub difficulty: Difficulty,
-    pub visual_pile_locks: [Option<usize>; 10],
+    pub mode: GameMode,
+    pub seed: u64,
+    pub scoring: ScoringScheme,
+    pub elapsed_secs: f32,
 }
 
 #[derive(Resource, Default)]
@@ -67,11 +126,6 @@ pub struct GameHistory {
     pub stack: Vec<SpiderGame>,
 }
 
-#[derive(Resource, Default)]
-pub struct SequenceCheckRequest {
-    pub pending: bool,
-}
-
 #[derive(Resource)]
 pub struct AnimationEventQueue {
     pub timer: Timer,
@@ -87,17 +141,26 @@ impl Default for AnimationEventQueue {
     }
 }
 
-#[derive(Resource)]
-pub struct GameSounds {
-    pub deal: Handle<AudioSource>,
-    pub drop: Handle<AudioSource>,
-}
-
 #[derive(Resource, Default)]
 pub struct GameAssets {
+    pub faces: HashMap<(Suit, Rank), Handle<Image>>,
     pub back_texture: Handle<Image>,
     pub blank_texture: Handle<Image>,
     pub font: Handle<Font>,
+    pub background: Color,
+}
+
+impl GameAssets {
+    pub fn card_texture(&self, card: &Card) -> Handle<Image> {
+        if card.face_up {
+            self.faces
+                .get(&(card.suit, card.rank))
+                .cloned()
+                .unwrap_or_default()
+        } else {
+            self.back_texture.clone()
+        }
+    }
 }
 
 #[derive(Resource, Default)]
@@ -115,10 +178,13 @@ impl Default for SpiderGame {
             drag_pos: None,
             drag_offset: None,
             move_count: 0,
+            undo_count: 0,
             score: 500,
-            auto_move_to: None,
             difficulty: Difficulty::Easy,
-            visual_pile_locks: [None; 10],
+            mode: GameMode::Classic,
+            seed: 0,
+            scoring: ScoringScheme::Standard,
+            elapsed_secs: 0.0,
         }
     }
 }
@@ -128,7 +194,124 @@ impl SpiderGame {
         self.tableau.get(index)?.last()
     }
 
+    pub fn can_undo(&self) -> bool {
+        self.scoring
+            .undo_limit()
+            .is_none_or(|limit| self.undo_count < limit)
+    }
+
     pub fn is_won(&self) -> bool {
         self.foundations.len() == 8
     }
+
+    pub fn is_lost(&self) -> bool {
+        self.scoring.can_lose() && self.score <= 0
+    }
+
+    pub fn is_movable_run(&self, col: usize, index: usize) -> bool {
+        let Some(run) = self.tableau.get(col).and_then(|pile| pile.get(index..)) else {
+            return false;
+        };
+        !run.is_empty()
+            && run.iter().all(|card| card.face_up)
+            && run
+                .windows(2)
+                .all(|pair| pair[0].suit == pair[1].suit && pair[1].can_stack_on(&pair[0]))
+    }
+
+    pub fn can_move(&self, from_col: usize, index: usize, to_col: usize) -> bool {
+        from_col != to_col
+            && to_col < self.tableau.len()
+            && self.is_movable_run(from_col, index)
+            && self.tableau[to_col]
+                .last()
+                .is_none_or(|top| self.tableau[from_col][index].can_stack_on(top))
+    }
+
+    pub fn legal_moves(&self) -> Vec<(usize, usize, usize)> {
+        let columns = self.tableau.len();
+        let mut moves = Vec::new();
+        for from in 0..columns {
+            for index in 0..self.tableau[from].len() {
+                for to in 0..columns {
+                    if self.can_move(from, index, to) {
+                        moves.push((from, index, to));
+                    }
+                }
+            }
+        }
+        moves
+    }
+
+    pub fn move_cards(
+        &mut self,
+        from_col: usize,
+        index: usize,
+        to_col: usize,
+    ) -> Option<MovedCards> {
+        if !self.can_move(from_col, index, to_col) {
+            return None;
+        }
+        let cards: Vec<Card> = self.tableau[from_col].drain(index..).collect();
+        let revealed = self.reveal_top(from_col);
+        self.tableau[to_col].extend(cards.iter().copied());
+        self.move_count += 1;
+        self.score -= self.scoring.move_penalty();
+        Some(MovedCards { cards, revealed })
+    }
+
+    pub fn deal_tableau(&mut self) -> Vec<(usize, Card)> {
+        let mut deck = Card::shuffled_spider_deck(self.difficulty, self.seed);
+        let mut dealt = Vec::new();
+        for i in 0..54 {
+            let column = i % 10;
+            let Some(mut card) = deck.pop() else {
+                break;
+            };
+            card.face_up = if column < 4 { i >= 50 } else { i >= 44 };
+            self.tableau[column].push(card);
+            dealt.push((column, card));
+        }
+        self.stock = deck;
+        dealt
+    }
+
+    pub fn undo_to(&mut self, previous: SpiderGame) {
+        let current = std::mem::replace(self, previous);
+        self.difficulty = current.difficulty;
+        self.elapsed_secs = current.elapsed_secs;
+        self.undo_count = current.undo_count + 1;
+        if self.scoring.undo_counts_as_move() {
+            self.score -= self.scoring.move_penalty();
+            self.move_count += 1;
+        }
+    }
+
+    pub fn deal_row(&mut self) -> Vec<Card> {
+        let mut dealt = Vec::new();
+        for column in self.tableau.iter_mut() {
+            let Some(mut card) = self.stock.pop() else {
+                break;
+            };
+            card.face_up = true;
+            column.push(card);
+            dealt.push(card);
+        }
+        dealt
+    }
+
+    pub fn reveal_top(&mut self, col: usize) -> bool {
+        match self.tableau[col].last_mut() {
+            Some(top) if !top.face_up => {
+                top.face_up = true;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+pub struct MovedCards {
+    pub cards: Vec<Card>,
+    pub revealed: bool,
 }
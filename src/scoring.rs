@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+const STANDARD_START: i32 = 500;
+const VEGAS_ANTE: i32 = 104;
+const VEGAS_SUIT_PAYOUT: i32 = 26;
+const VEGAS_UNDO_LIMIT: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum ScoringScheme {
+    #[default]
+    Standard,
+    Windows,
+    Vegas,
+    Relaxed,
+}
+
+impl ScoringScheme {
+    pub fn all() -> [ScoringScheme; 4] {
+        [
+            ScoringScheme::Standard,
+            ScoringScheme::Windows,
+            ScoringScheme::Vegas,
+            ScoringScheme::Relaxed,
+        ]
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            ScoringScheme::Standard => "Standard",
+            ScoringScheme::Windows => "Windows",
+            ScoringScheme::Vegas => "Vegas",
+            ScoringScheme::Relaxed => "Relaxed",
+        }
+    }
+
+    pub fn next(&self) -> ScoringScheme {
+        let all = Self::all();
+        let index = all.iter().position(|s| s == self).unwrap();
+        all[(index + 1) % all.len()]
+    }
+
+    pub fn starting_score(&self, vegas_balance: i32) -> i32 {
+        match self {
+            ScoringScheme::Standard | ScoringScheme::Windows => STANDARD_START,
+            ScoringScheme::Vegas => vegas_balance - VEGAS_ANTE,
+            ScoringScheme::Relaxed => 0,
+        }
+    }
+
+    pub fn move_penalty(&self) -> i32 {
+        match self {
+            ScoringScheme::Standard | ScoringScheme::Windows => 1,
+            ScoringScheme::Vegas | ScoringScheme::Relaxed => 0,
+        }
+    }
+
+    pub fn suit_bonus(&self) -> i32 {
+        match self {
+            ScoringScheme::Standard | ScoringScheme::Windows => 100,
+            ScoringScheme::Vegas => VEGAS_SUIT_PAYOUT,
+            ScoringScheme::Relaxed => 0,
+        }
+    }
+
+    pub fn undo_counts_as_move(&self) -> bool {
+        matches!(self, ScoringScheme::Windows)
+    }
+
+    pub fn undo_limit(&self) -> Option<u32> {
+        match self {
+            ScoringScheme::Vegas => Some(VEGAS_UNDO_LIMIT),
+            _ => None,
+        }
+    }
+
+    pub fn can_lose(&self) -> bool {
+        matches!(self, ScoringScheme::Standard)
+    }
+
+    pub fn format_score(&self, score: i32) -> String {
+        match self {
+            ScoringScheme::Vegas if score < 0 => format!("-${}", -score),
+            ScoringScheme::Vegas => format!("${}", score),
+            ScoringScheme::Relaxed => "-".to_string(),
+            _ => score.to_string(),
+        }
+    }
+}
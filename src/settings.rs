@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::animation::{AnimationSpeed, Easing};
+use crate::scoring::ScoringScheme;
+use crate::storage;
+use crate::theme::DEFAULT_THEME;
+
+pub const SETTINGS_FILE: &str = "settings.json";
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(storage::load::<Settings>(SETTINGS_FILE))
+            .add_systems(Update, save_settings);
+    }
+}
+
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub winnable_deals: bool,
+    pub scoring: ScoringScheme,
+    pub theme: String,
+    pub procedural_cards: bool,
+    pub four_colour_deck: bool,
+    pub high_contrast: bool,
+    pub card_patterns: bool,
+    pub move_log: bool,
+    pub collapse_long_columns: bool,
+    pub master_volume: f32,
+    pub effects_volume: f32,
+    pub music_volume: f32,
+    pub muted: bool,
+    pub animation_speed: AnimationSpeed,
+    pub easing: Easing,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            winnable_deals: false,
+            scoring: ScoringScheme::default(),
+            theme: DEFAULT_THEME.to_string(),
+            procedural_cards: false,
+            four_colour_deck: false,
+            high_contrast: false,
+            card_patterns: false,
+            move_log: false,
+            collapse_long_columns: false,
+            master_volume: 1.0,
+            effects_volume: 1.0,
+            music_volume: 0.5,
+            muted: false,
+            animation_speed: AnimationSpeed::default(),
+            easing: Easing::default(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn drawn_cards(&self) -> bool {
+        self.procedural_cards || self.four_colour_deck || self.high_contrast || self.card_patterns
+    }
+}
+
+fn save_settings(settings: Res<Settings>) {
+    if settings.is_changed() && !settings.is_added() {
+        storage::save(SETTINGS_FILE, &*settings);
+    }
+}
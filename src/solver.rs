@@ -0,0 +1,317 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BinaryHeap, HashSet};
+use std::hash::{Hash, Hasher};
+
+use crate::models::{Card, Rank};
+use crate::resources::Difficulty;
+
+const MAX_DEPTH: usize = 350;
+const STAGE_NODES: usize = 3000;
+const STAGE_BRANCHES: usize = 4;
+
+#[derive(Clone)]
+struct Board {
+    tableau: [Vec<Card>; 10],
+    stock: Vec<Card>,
+    completed: usize,
+}
+
+impl Board {
+    fn deal(difficulty: Difficulty, seed: u64) -> Self {
+        let mut deck = Card::shuffled_spider_deck(difficulty, seed);
+        let mut tableau: [Vec<Card>; 10] = Default::default();
+        for i in 0..54 {
+            let mut card = deck.pop().unwrap();
+            card.face_up = if i % 10 < 4 { i >= 50 } else { i >= 44 };
+            tableau[i % 10].push(card);
+        }
+        Self {
+            tableau,
+            stock: deck,
+            completed: 0,
+        }
+    }
+
+    fn key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.stock.len().hash(&mut hasher);
+        for col in &self.tableau {
+            col.hash(&mut hasher);
+            0xFFu8.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    fn run_start(&self, col: usize) -> Option<usize> {
+        let pile = &self.tableau[col];
+        let mut start = pile.len().checked_sub(1)?;
+        while start > 0 {
+            let below = pile[start - 1];
+            let above = pile[start];
+            if !below.face_up || below.suit != above.suit || !above.can_stack_on(&below) {
+                break;
+            }
+            start -= 1;
+        }
+        Some(start)
+    }
+
+    fn settle(&mut self, col: usize) {
+        let pile = &mut self.tableau[col];
+        if pile.len() >= 13 {
+            let start = pile.len() - 13;
+            let run = &pile[start..];
+            let complete = run[0].rank == Rank::King
+                && run.iter().all(|c| c.face_up && c.suit == run[0].suit)
+                && run.windows(2).all(|w| w[1].can_stack_on(&w[0]));
+            if complete {
+                pile.truncate(start);
+                self.completed += 1;
+            }
+        }
+        if let Some(top) = self.tableau[col].last_mut() {
+            top.face_up = true;
+        }
+    }
+
+    fn deal_row(&self) -> Board {
+        let mut next = self.clone();
+        for col in 0..10 {
+            if let Some(mut card) = next.stock.pop() {
+                card.face_up = true;
+                next.tableau[col].push(card);
+                next.settle(col);
+            }
+        }
+        next
+    }
+
+    fn successors(&self) -> Vec<(i32, Board)> {
+        let mut moves = Vec::new();
+        let first_empty = self.tableau.iter().position(|col| col.is_empty());
+
+        for from in 0..10 {
+            let Some(run_start) = self.run_start(from) else {
+                continue;
+            };
+            let pile = &self.tableau[from];
+            let below = run_start.checked_sub(1).map(|i| pile[i]);
+            let exposes = below.is_some_and(|c| !c.face_up);
+            let sits_in_sequence =
+                below.is_some_and(|c| c.face_up && pile[run_start].can_stack_on(&c));
+
+            for (start, &moving) in pile.iter().enumerate().skip(run_start) {
+                let partial = start > run_start;
+
+                for to in 0..10 {
+                    if to == from {
+                        continue;
+                    }
+                    let mut priority = match self.tableau[to].last() {
+                        Some(_) if partial => continue,
+                        Some(top) if moving.can_stack_on(top) => {
+                            let joins_suit = top.suit == moving.suit;
+                            if sits_in_sequence && !joins_suit {
+                                continue;
+                            }
+                            if joins_suit {
+                                30
+                            } else {
+                                0
+                            }
+                        }
+                        Some(_) => continue,
+                        None => {
+                            if Some(to) != first_empty || start == 0 {
+                                continue;
+                            }
+                            -30
+                        }
+                    };
+                    if !partial && exposes {
+                        priority += 50;
+                    }
+                    if start == 0 {
+                        priority += 40;
+                    }
+
+                    let mut next = self.clone();
+                    let cards: Vec<Card> = next.tableau[from].drain(start..).collect();
+                    next.tableau[to].extend(cards);
+                    next.settle(from);
+                    let before = next.completed;
+                    next.settle(to);
+                    if next.completed > before {
+                        priority += 1000;
+                    }
+                    moves.push((priority, next));
+                }
+            }
+        }
+
+        moves
+    }
+
+    fn evaluate(&self) -> i32 {
+        let mut score = self.completed as i32 * 500 + self.stock.len() as i32;
+        for pile in &self.tableau {
+            if pile.is_empty() {
+                score += 25;
+                continue;
+            }
+            for (i, card) in pile.iter().enumerate() {
+                if !card.face_up {
+                    score -= 15;
+                    continue;
+                }
+                if let Some(next) = pile.get(i + 1) {
+                    if next.can_stack_on(card) {
+                        score += if next.suit == card.suit { 8 } else { 2 };
+                    }
+                }
+            }
+        }
+        score
+    }
+}
+
+struct Node {
+    priority: i32,
+    depth: usize,
+    board: Board,
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl Eq for Node {}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority)
+    }
+}
+
+struct Search {
+    visited: HashSet<u64>,
+    expanded: usize,
+    max_nodes: usize,
+}
+
+impl Search {
+    fn solve_stage(&mut self, board: Board, depth: usize) -> bool {
+        let mut frontier = BinaryHeap::new();
+        let mut reached = Vec::new();
+        frontier.push(Node {
+            priority: board.evaluate(),
+            depth,
+            board,
+        });
+
+        let mut stage_expanded = 0;
+        while let Some(node) = frontier.pop() {
+            if node.board.completed == 8 {
+                return true;
+            }
+            if self.expanded >= self.max_nodes {
+                return false;
+            }
+            if stage_expanded >= STAGE_NODES {
+                break;
+            }
+            self.expanded += 1;
+            stage_expanded += 1;
+
+            if node.depth < MAX_DEPTH {
+                for (bonus, next) in node.board.successors() {
+                    if self.visited.insert(next.key()) {
+                        frontier.push(Node {
+                            priority: next.evaluate() + bonus / 10 - node.depth as i32,
+                            depth: node.depth + 1,
+                            board: next,
+                        });
+                    }
+                }
+            }
+            if !node.board.stock.is_empty() {
+                reached.push(node);
+            }
+        }
+
+        reached.sort_by_key(|node| Reverse(node.board.evaluate()));
+        for node in reached.into_iter().take(STAGE_BRANCHES) {
+            let dealt = node.board.deal_row();
+            if self.visited.insert(dealt.key()) && self.solve_stage(dealt, node.depth + 1) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+fn solve(board: Board, max_nodes: usize) -> bool {
+    let mut search = Search {
+        visited: HashSet::new(),
+        expanded: 0,
+        max_nodes,
+    };
+    search.solve_stage(board, 0)
+}
+
+pub fn is_winnable(difficulty: Difficulty, seed: u64, max_nodes: usize) -> bool {
+    solve(Board::deal(difficulty, seed), max_nodes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Suit;
+
+    fn face_up(suit: Suit, rank: Rank) -> Card {
+        Card {
+            face_up: true,
+            ..Card::new(suit, rank)
+        }
+    }
+
+    fn board(tableau: [Vec<Card>; 10], completed: usize) -> Board {
+        Board {
+            tableau,
+            stock: Vec::new(),
+            completed,
+        }
+    }
+
+    #[test]
+    fn finishes_the_last_suit() {
+        let mut tableau: [Vec<Card>; 10] = Default::default();
+        tableau[0] = Rank::all()
+            .into_iter()
+            .skip(1)
+            .rev()
+            .map(|rank| face_up(Suit::Spades, rank))
+            .collect();
+        tableau[5] = vec![face_up(Suit::Spades, Rank::Ace)];
+        assert!(solve(board(tableau, 7), 1000));
+    }
+
+    #[test]
+    fn gives_up_when_no_move_is_left() {
+        let mut tableau: [Vec<Card>; 10] = Default::default();
+        for (col, pile) in tableau.iter_mut().enumerate() {
+            let suit = Suit::all()[col % 4];
+            *pile = vec![Card::new(suit, Rank::Ace), face_up(suit, Rank::King)];
+        }
+        assert!(!solve(board(tableau, 0), 1000));
+    }
+}
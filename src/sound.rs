@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+use bevy::asset::io::file::FileAssetReader;
+use bevy::audio::Volume;
+use bevy::prelude::*;
+
+use crate::resources::{GameState, SpiderGame};
+use crate::settings::Settings;
+use crate::systems::{
+    deal_stock_system, undo_system, DealEvent, DealStockEvent, InvalidMoveEvent,
+    SuitCompletedEvent, UndoEvent,
+};
+
+const SOUNDS_DIR: &str = "sounds";
+const MAX_VOICES: usize = 8;
+
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<PlaySound>()
+            .add_systems(Startup, load_sounds)
+            .add_systems(
+                Update,
+                (
+                    toggle_mute,
+                    cue_game_events.after(undo_system).after(deal_stock_system),
+                    play_sounds,
+                )
+                    .chain(),
+            )
+            .add_systems(OnEnter(GameState::Won), play_win)
+            .add_systems(OnEnter(GameState::Lost), play_loss);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundCue {
+    Deal,
+    Drop,
+    PickUp,
+    Invalid,
+    SuitComplete,
+    Win,
+    Loss,
+    Undo,
+}
+
+impl SoundCue {
+    pub fn all() -> [SoundCue; 8] {
+        [
+            SoundCue::Deal,
+            SoundCue::Drop,
+            SoundCue::PickUp,
+            SoundCue::Invalid,
+            SoundCue::SuitComplete,
+            SoundCue::Win,
+            SoundCue::Loss,
+            SoundCue::Undo,
+        ]
+    }
+
+    fn file_name(&self) -> &str {
+        match self {
+            SoundCue::Deal => "deal",
+            SoundCue::Drop => "drop",
+            SoundCue::PickUp => "pickup",
+            SoundCue::Invalid => "invalid",
+            SoundCue::SuitComplete => "suit_complete",
+            SoundCue::Win => "win",
+            SoundCue::Loss => "loss",
+            SoundCue::Undo => "undo",
+        }
+    }
+
+    fn fallback(&self) -> SoundCue {
+        match self {
+            SoundCue::Deal | SoundCue::SuitComplete | SoundCue::Win => SoundCue::Deal,
+            SoundCue::Drop
+            | SoundCue::PickUp
+            | SoundCue::Invalid
+            | SoundCue::Loss
+            | SoundCue::Undo => SoundCue::Drop,
+        }
+    }
+
+    fn min_interval(&self) -> f32 {
+        match self {
+            SoundCue::Win | SoundCue::Loss => 1.0,
+            _ => 0.05,
+        }
+    }
+}
+
+#[derive(Message)]
+pub struct PlaySound(pub SoundCue);
+
+#[derive(Resource)]
+pub struct GameSounds {
+    pub cues: HashMap<SoundCue, Handle<AudioSource>>,
+}
+
+#[derive(Component)]
+struct SoundVoice(SoundCue);
+
+fn sound_path(cue: SoundCue) -> String {
+    format!("{}/{}.ogg", SOUNDS_DIR, cue.file_name())
+}
+
+fn load_sounds(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let base = FileAssetReader::get_base_path().join("assets");
+    let cues = SoundCue::all()
+        .into_iter()
+        .map(|cue| {
+            let file_cue = if base.join(sound_path(cue)).exists() {
+                cue
+            } else {
+                cue.fallback()
+            };
+            (cue, asset_server.load(sound_path(file_cue)))
+        })
+        .collect();
+    commands.insert_resource(GameSounds { cues });
+}
+
+pub fn effects_volume(settings: &Settings) -> f32 {
+    if settings.muted {
+        0.0
+    } else {
+        settings.master_volume * settings.effects_volume
+    }
+}
+
+fn toggle_mute(keyboard: Res<ButtonInput<KeyCode>>, mut settings: ResMut<Settings>) {
+    if keyboard.just_pressed(KeyCode::KeyM) {
+        settings.muted = !settings.muted;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn cue_game_events(
+    game: Res<SpiderGame>,
+    mut deals: MessageReader<DealEvent>,
+    mut stock_deals: MessageReader<DealStockEvent>,
+    mut invalid_moves: MessageReader<InvalidMoveEvent>,
+    mut suits: MessageReader<SuitCompletedEvent>,
+    mut undos: MessageReader<UndoEvent>,
+    mut last_undo_count: Local<u32>,
+    mut last_stock_len: Local<usize>,
+    mut writer: MessageWriter<PlaySound>,
+) {
+    let dealt_row = stock_deals.read().count() > 0 && game.stock.len() < *last_stock_len;
+    if deals.read().count() > 0 || dealt_row {
+        writer.write(PlaySound(SoundCue::Deal));
+    }
+    if invalid_moves.read().count() > 0 {
+        writer.write(PlaySound(SoundCue::Invalid));
+    }
+    if suits.read().count() > 0 {
+        writer.write(PlaySound(SoundCue::SuitComplete));
+    }
+    if undos.read().count() > 0 && game.undo_count > *last_undo_count {
+        writer.write(PlaySound(SoundCue::Undo));
+    }
+    *last_undo_count = game.undo_count;
+    *last_stock_len = game.stock.len();
+}
+
+fn play_win(mut writer: MessageWriter<PlaySound>) {
+    writer.write(PlaySound(SoundCue::Win));
+}
+
+fn play_loss(mut writer: MessageWriter<PlaySound>) {
+    writer.write(PlaySound(SoundCue::Loss));
+}
+
+fn play_sounds(
+    mut commands: Commands,
+    mut requests: MessageReader<PlaySound>,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    game_sounds: Res<GameSounds>,
+    voices: Query<&SoundVoice>,
+    mut last_played: Local<HashMap<SoundCue, f32>>,
+) {
+    let volume = effects_volume(&settings);
+    let now = time.elapsed_secs();
+    let mut playing: Vec<SoundCue> = voices.iter().map(|voice| voice.0).collect();
+
+    for PlaySound(cue) in requests.read() {
+        if volume <= 0.0 || playing.len() >= MAX_VOICES {
+            continue;
+        }
+        if last_played
+            .get(cue)
+            .is_some_and(|last| now - last < cue.min_interval())
+        {
+            continue;
+        }
+        if playing.contains(cue) {
+            continue;
+        }
+        let Some(sound) = game_sounds.cues.get(cue) else {
+            continue;
+        };
+        commands.spawn((
+            AudioPlayer(sound.clone()),
+            PlaybackSettings::DESPAWN.with_volume(Volume::Linear(volume)),
+            SoundVoice(*cue),
+        ));
+        last_played.insert(*cue, now);
+        playing.push(*cue);
+    }
+}
@@ -0,0 +1,335 @@
+use bevy::prelude::*;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::daily::today;
+use crate::resources::{Difficulty, GameAssets, GameMode, GameState, SpiderGame};
+use crate::scoring::ScoringScheme;
+use crate::storage;
+use crate::theme::ThemeBackground;
+use crate::ui::format_time;
+
+pub const STATS_FILE: &str = "stats.json";
+const RECENT_GAMES: usize = 8;
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(storage::load::<Statistics>(STATS_FILE))
+            .add_systems(OnExit(GameState::Playing), record_game)
+            .add_systems(Update, (sync_vegas_balance, save_statistics).chain())
+            .add_systems(OnEnter(GameState::Statistics), setup_stats_screen)
+            .add_systems(
+                Update,
+                handle_stats_interaction.run_if(in_state(GameState::Statistics)),
+            )
+            .add_systems(OnExit(GameState::Statistics), cleanup_stats_screen);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub date: NaiveDate,
+    pub difficulty: Difficulty,
+    pub scoring: ScoringScheme,
+    pub daily: bool,
+    pub score: i32,
+    pub moves: u32,
+    #[serde(default)]
+    pub undos: u32,
+    pub time_secs: u32,
+    pub won: bool,
+}
+
+impl GameRecord {
+    pub fn won_without_undo(&self) -> bool {
+        self.won && self.undos == 0
+    }
+}
+
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Statistics {
+    pub records: Vec<GameRecord>,
+    pub vegas_balance: i32,
+}
+
+impl Statistics {
+    pub fn record(&mut self, game: &SpiderGame) {
+        if game.move_count == 0 && !game.is_won() {
+            return;
+        }
+        self.records.push(GameRecord {
+            date: today(),
+            difficulty: game.difficulty,
+            scoring: game.scoring,
+            daily: matches!(game.mode, GameMode::Daily(_)),
+            score: game.score,
+            moves: game.move_count,
+            undos: game.undo_count,
+            time_secs: game.elapsed_secs as u32,
+            won: game.is_won(),
+        });
+    }
+
+    pub fn sync_vegas_balance(&mut self, game: &SpiderGame) -> bool {
+        let changed = game.scoring == ScoringScheme::Vegas && self.vegas_balance != game.score;
+        if changed {
+            self.vegas_balance = game.score;
+        }
+        changed
+    }
+}
+
+fn record_game(game: Res<SpiderGame>, mut stats: ResMut<Statistics>) {
+    stats.record(&game);
+}
+
+fn sync_vegas_balance(game: Res<SpiderGame>, mut stats: ResMut<Statistics>) {
+    if stats.bypass_change_detection().sync_vegas_balance(&game) {
+        stats.set_changed();
+    }
+}
+
+fn save_statistics(stats: Res<Statistics>) {
+    if stats.is_changed() && !stats.is_added() {
+        storage::save(STATS_FILE, &*stats);
+    }
+}
+
+#[derive(Component)]
+struct StatsEntity;
+
+#[derive(Component)]
+struct StatsBackButton;
+
+fn spawn_table(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    headers: &[&str],
+    rows: Vec<Vec<String>>,
+) {
+    parent
+        .spawn((
+            Node {
+                display: Display::Grid,
+                grid_template_columns: RepeatedGridTrack::px(headers.len() as u16, 150.0),
+                row_gap: Val::Px(6.0),
+                column_gap: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(15.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.3)),
+        ))
+        .with_children(|grid| {
+            let header_color = Color::srgb(222. / 255., 154. / 255., 40. / 255.);
+            let cells = headers
+                .iter()
+                .map(|h| (h.to_string(), header_color))
+                .chain(rows.into_iter().flatten().map(|c| (c, Color::WHITE)));
+            for (label, color) in cells {
+                grid.spawn((
+                    Text::new(label),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 20.0,
+                        ..default()
+                    },
+                    TextColor(color),
+                ));
+            }
+        });
+}
+
+fn setup_stats_screen(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    stats: Res<Statistics>,
+) {
+    let font = game_assets.font.clone();
+
+    let difficulty_rows = Difficulty::all()
+        .into_iter()
+        .map(|diff| {
+            let games: Vec<&GameRecord> = stats
+                .records
+                .iter()
+                .filter(|r| r.difficulty == diff)
+                .collect();
+            let wins: Vec<&&GameRecord> = games.iter().filter(|r| r.won).collect();
+            let win_rate = if games.is_empty() {
+                0
+            } else {
+                wins.len() * 100 / games.len()
+            };
+            vec![
+                diff.label().to_string(),
+                games.len().to_string(),
+                wins.len().to_string(),
+                format!("{}%", win_rate),
+                wins.iter()
+                    .filter(|r| r.won_without_undo())
+                    .count()
+                    .to_string(),
+                wins.iter()
+                    .map(|r| r.time_secs)
+                    .min()
+                    .map_or("-".to_string(), |t| format_time(t as f32)),
+                wins.iter()
+                    .map(|r| r.moves)
+                    .min()
+                    .map_or("-".to_string(), |m| m.to_string()),
+            ]
+        })
+        .collect();
+
+    let scoring_rows = ScoringScheme::all()
+        .into_iter()
+        .map(|scheme| {
+            let games: Vec<&GameRecord> = stats
+                .records
+                .iter()
+                .filter(|r| r.scoring == scheme)
+                .collect();
+            vec![
+                scheme.label().to_string(),
+                games.len().to_string(),
+                games.iter().filter(|r| r.won).count().to_string(),
+                games
+                    .iter()
+                    .map(|r| r.score)
+                    .max()
+                    .filter(|_| scheme != ScoringScheme::Relaxed)
+                    .map_or("-".to_string(), |s| scheme.format_score(s)),
+            ]
+        })
+        .collect();
+
+    let recent_rows = stats
+        .records
+        .iter()
+        .rev()
+        .take(RECENT_GAMES)
+        .map(|r| {
+            vec![
+                r.date.format("%Y-%m-%d").to_string(),
+                r.difficulty.label().to_string(),
+                r.scoring.label().to_string(),
+                r.scoring.format_score(r.score),
+                r.moves.to_string(),
+                r.undos.to_string(),
+                format_time(r.time_secs as f32),
+                if r.won { "Won" } else { "Lost" }.to_string(),
+            ]
+        })
+        .collect();
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(15.0),
+                ..default()
+            },
+            BackgroundColor(game_assets.background),
+            ThemeBackground,
+            StatsEntity,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Statistics"),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 60.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            spawn_table(
+                parent,
+                &font,
+                &[
+                    "Suits",
+                    "Played",
+                    "Won",
+                    "Win Rate",
+                    "No-Undo Wins",
+                    "Best Time",
+                    "Fewest Moves",
+                ],
+                difficulty_rows,
+            );
+            spawn_table(
+                parent,
+                &font,
+                &["Scoring", "Played", "Won", "Best Score"],
+                scoring_rows,
+            );
+            parent.spawn((
+                Text::new(format!(
+                    "Vegas balance: {}",
+                    ScoringScheme::Vegas.format_score(stats.vegas_balance)
+                )),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            spawn_table(
+                parent,
+                &font,
+                &[
+                    "Date", "Suits", "Scoring", "Score", "Moves", "Undos", "Time", "Result",
+                ],
+                recent_rows,
+            );
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(300.0),
+                        height: Val::Px(60.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.4, 0.4, 0.4)),
+                    StatsBackButton,
+                ))
+                .with_children(|p| {
+                    p.spawn((
+                        Text::new("Back"),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 25.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+}
+
+fn handle_stats_interaction(
+    mut next_state: ResMut<NextState<GameState>>,
+    query: Query<&Interaction, (Changed<Interaction>, With<StatsBackButton>)>,
+) {
+    for interaction in query.iter() {
+        if *interaction == Interaction::Pressed {
+            next_state.set(GameState::Menu);
+        }
+    }
+}
+
+fn cleanup_stats_screen(mut commands: Commands, query: Query<Entity, With<StatsEntity>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
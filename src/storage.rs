@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+
+fn data_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("spider-solitaire"))
+}
+
+pub fn load<T: DeserializeOwned + Default>(file_name: &str) -> T {
+    let Some(path) = data_dir().map(|dir| dir.join(file_name)) else {
+        return T::default();
+    };
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return T::default();
+    };
+    match serde_json::from_str(&contents) {
+        Ok(value) => value,
+        Err(err) => {
+            warn!("Ignoring unreadable save file {}: {}", path.display(), err);
+            T::default()
+        }
+    }
+}
+
+pub fn save<T: Serialize>(file_name: &str, value: &T) {
+    let Some(dir) = data_dir() else {
+        return;
+    };
+    if let Err(err) = fs::create_dir_all(&dir) {
+        warn!("Could not create save directory {}: {}", dir.display(), err);
+        return;
+    }
+    let path = dir.join(file_name);
+    let result = serde_json::to_string_pretty(value)
+        .map_err(|err| err.to_string())
+        .and_then(|json| fs::write(&path, json).map_err(|err| err.to_string()));
+    if let Err(err) = result {
+        warn!("Could not write save file {}: {}", path.display(), err);
+    }
+}
@@ -0,0 +1,392 @@
+use arboard::Clipboard;
+use bevy::prelude::*;
+
+use crate::resources::{Difficulty, GameAssets, GameMode, GameState, SpiderGame};
+use crate::scoring::ScoringScheme;
+use crate::systems::DealEvent;
+use crate::ui::format_time;
+
+const CHART_BARS: usize = 60;
+const CHART_WIDTH: f32 = 480.0;
+const CHART_HEIGHT: f32 = 120.0;
+
+pub struct SummaryPlugin;
+
+impl Plugin for SummaryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<ShowSummary>()
+            .init_resource::<GameLog>()
+            .add_systems(Update, record_game_log)
+            .add_systems(OnEnter(GameState::Lost), request_summary)
+            .add_systems(
+                Update,
+                (show_summary, handle_summary_interaction)
+                    .run_if(in_state(GameState::Won).or(in_state(GameState::Lost))),
+            )
+            .add_systems(OnExit(GameState::Won), cleanup_summary)
+            .add_systems(OnExit(GameState::Lost), cleanup_summary);
+    }
+}
+
+#[derive(Message)]
+pub struct ShowSummary;
+
+#[derive(Resource, Default)]
+pub struct GameLog {
+    pub scores: Vec<(u32, i32)>,
+    pub suits: Vec<(f32, u32)>,
+}
+
+#[derive(Component)]
+struct SummaryEntity;
+
+#[derive(Component)]
+struct DifficultyPicker;
+
+#[derive(Component, Clone, Copy)]
+enum SummaryButton {
+    NewGame,
+    Difficulty(Difficulty),
+    SameDifficulty,
+    ReplayDeal,
+    CopySeed,
+    Menu,
+}
+
+fn record_game_log(game: Res<SpiderGame>, mut log: ResMut<GameLog>) {
+    if !game.is_changed() {
+        return;
+    }
+    let point = (game.move_count, game.score);
+    if log.scores.last() != Some(&point) {
+        log.scores.retain(|(moves, _)| *moves < game.move_count);
+        log.scores.push(point);
+    }
+    log.suits.truncate(game.foundations.len());
+    while log.suits.len() < game.foundations.len() {
+        log.suits.push((game.elapsed_secs, game.move_count));
+    }
+}
+
+fn request_summary(mut summary_writer: MessageWriter<ShowSummary>) {
+    summary_writer.write(ShowSummary);
+}
+
+fn spawn_label(parent: &mut ChildSpawnerCommands, font: &Handle<Font>, text: String, size: f32) {
+    parent.spawn((
+        Text::new(text),
+        TextFont {
+            font: font.clone(),
+            font_size: size,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+    ));
+}
+
+fn spawn_score_chart(parent: &mut ChildSpawnerCommands, font: &Handle<Font>, log: &GameLog) {
+    let Some(&(last_move, _)) = log.scores.last() else {
+        return;
+    };
+    let min = log.scores.iter().map(|(_, s)| *s).min().unwrap_or(0);
+    let max = log.scores.iter().map(|(_, s)| *s).max().unwrap_or(0);
+    let range = (max - min).max(1) as f32;
+    let per_bar = (last_move as usize + 1).div_ceil(CHART_BARS).max(1) as u32;
+    let suit_color = Color::srgb(222. / 255., 154. / 255., 40. / 255.);
+    let bar_color = Color::srgb(93. / 255., 140. / 255., 160. / 255.);
+
+    spawn_label(
+        parent,
+        font,
+        format!("Score by move (low {}, high {})", min, max),
+        18.0,
+    );
+    parent
+        .spawn((
+            Node {
+                width: Val::Px(CHART_WIDTH),
+                height: Val::Px(CHART_HEIGHT),
+                align_items: AlignItems::FlexEnd,
+                column_gap: Val::Px(1.0),
+                padding: UiRect::all(Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.1)),
+        ))
+        .with_children(|chart| {
+            let mut bucket_start = 0;
+            while bucket_start <= last_move {
+                let bucket_end = bucket_start + per_bar;
+                let score = log
+                    .scores
+                    .iter()
+                    .take_while(|(moves, _)| *moves < bucket_end)
+                    .last()
+                    .map_or(min, |(_, s)| *s);
+                let completed_suit = log
+                    .suits
+                    .iter()
+                    .any(|(_, moves)| (bucket_start..bucket_end).contains(moves));
+                let height = 2.0 + (score - min) as f32 / range * (CHART_HEIGHT - 10.0);
+                chart.spawn((
+                    Node {
+                        flex_grow: 1.0,
+                        height: Val::Px(height),
+                        ..default()
+                    },
+                    BackgroundColor(if completed_suit {
+                        suit_color
+                    } else {
+                        bar_color
+                    }),
+                ));
+                bucket_start = bucket_end;
+            }
+        });
+    spawn_label(parent, font, format!("Moves 0 - {}", last_move), 16.0);
+}
+
+fn show_summary(
+    mut commands: Commands,
+    mut summary_events: MessageReader<ShowSummary>,
+    game_assets: Res<GameAssets>,
+    game: Res<SpiderGame>,
+    log: Res<GameLog>,
+) {
+    if summary_events.read().count() == 0 {
+        return;
+    }
+    let font = game_assets.font.clone();
+    let (title, title_color) = if game.is_won() {
+        ("YOU WIN!", Color::srgb(229. / 255., 93. / 255., 77. / 255.))
+    } else {
+        (
+            "YOU LOSE!",
+            Color::srgb(142. / 255., 146. / 255., 87. / 255.),
+        )
+    };
+    let line = format!(
+        "Score: {}   Moves: {}   Undos: {}   Time: {}",
+        game.scoring.format_score(game.score),
+        game.move_count,
+        game.undo_count,
+        format_time(game.elapsed_secs)
+    );
+    let suits = if log.suits.is_empty() {
+        "Suits completed: 0/8".to_string()
+    } else {
+        let times: Vec<String> = log
+            .suits
+            .iter()
+            .map(|(secs, moves)| format!("{} (move {})", format_time(*secs), moves))
+            .collect();
+        format!(
+            "Suits completed: {}/8 at {}",
+            log.suits.len(),
+            times.join(", ")
+        )
+    };
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            GlobalZIndex(2000),
+            SummaryEntity,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(12.0),
+                        padding: UiRect::all(Val::Px(30.0)),
+                        max_width: Val::Percent(80.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        Text::new(title),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 100.0,
+                            ..default()
+                        },
+                        TextColor(title_color),
+                    ));
+                    spawn_label(panel, &font, line, 28.0);
+                    spawn_label(panel, &font, suits, 20.0);
+                    if game.scoring != ScoringScheme::Relaxed {
+                        spawn_score_chart(panel, &font, &log);
+                    }
+                    spawn_label(panel, &font, format!("Deal seed: {}", game.seed), 20.0);
+                    panel
+                        .spawn(Node {
+                            column_gap: Val::Px(20.0),
+                            margin: UiRect::top(Val::Px(20.0)),
+                            ..default()
+                        })
+                        .with_children(|row| {
+                            let buttons = [
+                                (
+                                    SummaryButton::NewGame,
+                                    "New Game",
+                                    Color::srgb(142. / 255., 146. / 255., 87. / 255.),
+                                ),
+                                (
+                                    SummaryButton::SameDifficulty,
+                                    "Same Difficulty",
+                                    Color::srgb(142. / 255., 146. / 255., 87. / 255.),
+                                ),
+                                (
+                                    SummaryButton::ReplayDeal,
+                                    "Replay Deal",
+                                    Color::srgb(222. / 255., 154. / 255., 40. / 255.),
+                                ),
+                                (
+                                    SummaryButton::CopySeed,
+                                    "Copy Seed",
+                                    Color::srgb(93. / 255., 140. / 255., 160. / 255.),
+                                ),
+                                (SummaryButton::Menu, "Menu", Color::srgb(0.4, 0.4, 0.4)),
+                            ];
+                            for (button, label, color) in buttons {
+                                spawn_button(row, &font, button, label, color);
+                            }
+                        });
+                    panel
+                        .spawn((
+                            Node {
+                                display: Display::None,
+                                column_gap: Val::Px(20.0),
+                                ..default()
+                            },
+                            DifficultyPicker,
+                        ))
+                        .with_children(|row| {
+                            for difficulty in Difficulty::all() {
+                                spawn_button(
+                                    row,
+                                    &font,
+                                    SummaryButton::Difficulty(difficulty),
+                                    difficulty.label(),
+                                    Color::srgb(142. / 255., 146. / 255., 87. / 255.),
+                                );
+                            }
+                        });
+                });
+        });
+}
+
+fn spawn_button(
+    row: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    button: SummaryButton,
+    label: &str,
+    color: Color,
+) {
+    row.spawn((
+        Button,
+        Node {
+            width: Val::Px(200.0),
+            height: Val::Px(60.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(color),
+        button,
+    ))
+    .with_children(|p| {
+        spawn_label(p, font, label.to_string(), 25.0);
+    });
+}
+
+fn copy_seed(clipboard: &mut Option<Clipboard>, seed: u64) -> bool {
+    if clipboard.is_none() {
+        match Clipboard::new() {
+            Ok(new_clipboard) => *clipboard = Some(new_clipboard),
+            Err(err) => {
+                warn!("Clipboard unavailable: {}", err);
+                return false;
+            }
+        }
+    }
+    let Some(clipboard) = clipboard.as_mut() else {
+        return false;
+    };
+    match clipboard.set_text(seed.to_string()) {
+        Ok(()) => true,
+        Err(err) => {
+            warn!("Failed to copy seed: {}", err);
+            false
+        }
+    }
+}
+
+fn handle_summary_interaction(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut deal_writer: MessageWriter<DealEvent>,
+    mut clipboard: Local<Option<Clipboard>>,
+    game: Res<SpiderGame>,
+    button_query: Query<(&Interaction, &SummaryButton, &Children), Changed<Interaction>>,
+    mut text_query: Query<&mut Text>,
+    mut picker_query: Query<&mut Node, With<DifficultyPicker>>,
+) {
+    for (interaction, button, children) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            SummaryButton::NewGame => {
+                for mut node in picker_query.iter_mut() {
+                    node.display = Display::Flex;
+                }
+            }
+            SummaryButton::Difficulty(difficulty) => {
+                deal_writer.write(DealEvent(*difficulty, GameMode::Classic));
+                next_state.set(GameState::Playing);
+            }
+            SummaryButton::SameDifficulty => {
+                deal_writer.write(DealEvent(game.difficulty, GameMode::Classic));
+                next_state.set(GameState::Playing);
+            }
+            SummaryButton::ReplayDeal => {
+                let mode = match game.mode {
+                    GameMode::Daily(date) => GameMode::Daily(date),
+                    _ => GameMode::Seeded(game.seed),
+                };
+                deal_writer.write(DealEvent(game.difficulty, mode));
+                next_state.set(GameState::Playing);
+            }
+            SummaryButton::CopySeed => {
+                let label = if copy_seed(&mut clipboard, game.seed) {
+                    "Copied!"
+                } else {
+                    "Copy Failed"
+                };
+                for child in children.iter() {
+                    if let Ok(mut text) = text_query.get_mut(child) {
+                        **text = label.to_string();
+                    }
+                }
+            }
+            SummaryButton::Menu => next_state.set(GameState::Menu),
+        }
+    }
+}
+
+fn cleanup_summary(mut commands: Commands, query: Query<Entity, With<SummaryEntity>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -1,47 +1,136 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
-use rand::seq::SliceRandom;
 
-use crate::models::{Card, Rank};
+use crate::animation::{AnimationPace, CASCADE_DELAY, DISPATCH_INTERVAL};
+use crate::daily::daily_seed;
+use crate::models::{Card, Rank, Suit};
 use crate::resources::{
-    AnimationEventQueue, Difficulty, GameHistory, GameState, PileType, SequenceCheckRequest,
+    AnimationEventQueue, Difficulty, GameHistory, GameMode, GameState, Landing, PileType,
     SpiderGame, StartAnimationEvent,
 };
+use crate::settings::Settings;
+use crate::stats::Statistics;
 use crate::ui::MovingCard;
-use crate::ui::{CardTarget, ClickableCard};
+use crate::winnable::WinnableSeeds;
 
 #[derive(Message)]
-pub struct DealEvent(pub Difficulty);
+pub struct DealEvent(pub Difficulty, pub GameMode);
 #[derive(Message)]
 pub struct DealStockEvent;
 #[derive(Message)]
 pub struct UndoEvent;
 
 #[derive(Message)]
-pub struct AnimationFinishedEvent {
-    pub cards: Vec<Card>,
+pub struct MoveEvent {
+    pub from_col: usize,
+    pub index: usize,
+    pub to_col: usize,
+    pub drag_pos: Option<Vec2>,
+}
+
+#[derive(Message)]
+pub struct MoveAppliedEvent {
     pub from: PileType,
     pub to: PileType,
-    pub flip_final: bool,
+    pub card: Card,
+    pub count: usize,
+    pub revealed: bool,
+}
+
+#[derive(Message)]
+pub struct InvalidMoveEvent {
+    pub card: Card,
+    pub to: Option<PileType>,
+}
+
+#[derive(Message)]
+pub struct SuitCompletedEvent {
+    pub suit: Suit,
+    pub foundation: usize,
+    pub stock_remaining: usize,
+}
+
+#[derive(Message)]
+pub struct AnimationFinishedEvent;
+
+pub struct CompletedRun {
+    pub column: usize,
+    pub start: usize,
+    pub cards: Vec<Card>,
+    pub foundation: usize,
+}
+
+fn starts_under_flight(event: &StartAnimationEvent, in_flight: &[Landing]) -> bool {
+    (0..event.cards.len()).any(|i| {
+        in_flight
+            .iter()
+            .any(|landing| landing.covers(event.from, event.from_index + i))
+    })
 }
 
 pub fn animation_event_dispatcher(
     time: Res<Time>,
+    pace: Res<AnimationPace>,
     mut queue_res: ResMut<AnimationEventQueue>,
     mut event_writer: MessageWriter<StartAnimationEvent>,
+    moving_cards: Query<&MovingCard>,
 ) {
-    queue_res.timer.tick(time.delta());
-    if queue_res.timer.just_finished() && !queue_res.queue.is_empty() {
+    let mut in_flight: Vec<Landing> = moving_cards
+        .iter()
+        .filter(|moving| !moving.finished)
+        .map(MovingCard::landing)
+        .collect();
+    let ready = |queue_res: &AnimationEventQueue, in_flight: &[Landing]| {
+        queue_res
+            .queue
+            .last()
+            .is_some_and(|event| !starts_under_flight(event, in_flight))
+    };
+    if pace.instant() {
+        while ready(&queue_res, &in_flight) {
+            let event = queue_res.queue.pop().unwrap();
+            in_flight.push(event.landing());
+            event_writer.write(event);
+        }
+        return;
+    }
+    let interval = Duration::from_secs_f32(pace.duration(DISPATCH_INTERVAL));
+    let timer = &mut queue_res.bypass_change_detection().timer;
+    if timer.duration() != interval {
+        timer.set_duration(interval);
+    }
+    timer.tick(time.delta());
+    if queue_res.timer.just_finished() && ready(&queue_res, &in_flight) {
         let event = queue_res.queue.pop().unwrap();
         event_writer.write(event);
     }
 }
 
+pub fn deal_seed(
+    mode: GameMode,
+    difficulty: Difficulty,
+    settings: &Settings,
+    winnable_seeds: &mut WinnableSeeds,
+) -> u64 {
+    match mode {
+        GameMode::Classic if settings.winnable_deals => winnable_seeds.take(difficulty),
+        GameMode::Classic => rand::random(),
+        GameMode::Daily(date) => daily_seed(date, difficulty),
+        GameMode::Seeded(seed) => seed,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn deal_system(
     mut commands: Commands,
     mut game: ResMut<SpiderGame>,
     mut deal_events: MessageReader<DealEvent>,
     mut queue_res: ResMut<AnimationEventQueue>,
     mut history: ResMut<GameHistory>,
+    settings: Res<Settings>,
+    stats: Res<Statistics>,
+    mut winnable_seeds: ResMut<WinnableSeeds>,
     moving_cards: Query<Entity, With<MovingCard>>,
 ) {
     for ev in deal_events.read() {
@@ -51,46 +140,38 @@ pub fn deal_system(
 
         history.stack.clear();
         let current_difficulty = ev.0;
+        let seed = deal_seed(ev.1, current_difficulty, &settings, &mut winnable_seeds);
         *game = SpiderGame {
             difficulty: current_difficulty,
-            score: 500,
+            mode: ev.1,
+            seed,
+            scoring: settings.scoring,
+            score: settings.scoring.starting_score(stats.vegas_balance),
             move_count: 0,
             ..SpiderGame::default()
         };
 
-        let mut deck = Card::new_spider_deck(game.difficulty);
-        deck.shuffle(&mut rand::thread_rng());
-
-        queue_res.queue.clear();
-        let mut events = Vec::new();
-
-        let mut col_counts = [0; 10];
-
-        for i in 0..54 {
-            let column = i % 10;
-            let mut card = deck.pop().unwrap();
-            card.face_up = false;
-            let is_last = if column < 4 { i >= 50 } else { i >= 44 };
-
-            let target_idx = col_counts[column];
-            col_counts[column] += 1;
-
-            events.push(StartAnimationEvent {
-                cards: vec![card],
-                from: PileType::Stock,
-                to: PileType::Tableau(column),
-                from_index: 0,
-                visual_start_pos: None,
-                delay: 0.0,
-                flip_final: is_last,
-                original_pile_len: None,
-                target_index_override: Some(target_idx),
-                fly_z_override: Some(i as f32),
-            });
-        }
+        let mut events: Vec<StartAnimationEvent> = game
+            .deal_tableau()
+            .into_iter()
+            .enumerate()
+            .map(|(i, (column, mut card))| {
+                card.face_up = false;
+                StartAnimationEvent {
+                    cards: vec![card],
+                    from: PileType::Stock,
+                    to: PileType::Tableau(column),
+                    from_index: 0,
+                    visual_start_pos: None,
+                    delay: 0.0,
+                    original_pile_len: None,
+                    target_index_override: Some(i / 10),
+                    fly_z_override: Some(i as f32),
+                }
+            })
+            .collect();
         events.reverse();
         queue_res.queue = events;
-        game.stock = deck;
     }
 }
 
@@ -99,189 +180,102 @@ pub fn deal_stock_system(
     mut deal_stock_events: MessageReader<DealStockEvent>,
     mut queue_res: ResMut<AnimationEventQueue>,
     mut history: ResMut<GameHistory>,
+    pace: Res<AnimationPace>,
+    mut suit_writer: MessageWriter<SuitCompletedEvent>,
 ) {
     for _ in deal_stock_events.read() {
         if game.stock.is_empty() {
             continue;
         }
         history.stack.push((*game).clone());
+        let dealt = game.deal_row();
         let mut events = Vec::new();
-        for i in 0..10 {
-            if let Some(mut card) = game.stock.pop() {
-                card.face_up = true;
-
-                let target_idx = game.tableau[i].len();
-
-                events.push(StartAnimationEvent {
-                    cards: vec![card],
-                    from: PileType::Stock,
-                    to: PileType::Tableau(i),
-                    from_index: 0,
-                    visual_start_pos: None,
-                    delay: 0.0,
-                    flip_final: false,
-                    original_pile_len: None,
-                    target_index_override: Some(target_idx),
-                    fly_z_override: Some(i as f32),
-                });
-            }
+        for (i, card) in dealt.into_iter().enumerate() {
+            events.push(StartAnimationEvent {
+                cards: vec![card],
+                from: PileType::Stock,
+                to: PileType::Tableau(i),
+                from_index: 0,
+                visual_start_pos: None,
+                delay: 0.0,
+                original_pile_len: None,
+                target_index_override: Some(game.tableau[i].len() - 1),
+                fly_z_override: Some(i as f32),
+            });
         }
         events.reverse();
-        queue_res.queue.extend(events);
+        enqueue(&mut queue_res, events);
+        complete_runs(&mut game, &mut queue_res, &pace, &mut suit_writer);
     }
 }
 
-pub fn finish_animation_system(
+#[allow(clippy::too_many_arguments)]
+pub fn move_cards_system(
     mut game: ResMut<SpiderGame>,
-    mut events: MessageReader<AnimationFinishedEvent>,
-    mut next_state: ResMut<NextState<GameState>>,
-    _queue_res: ResMut<AnimationEventQueue>,
-    mut check_request: ResMut<SequenceCheckRequest>,
-) {
-    let mut any_finished = false;
-    for ev in events.read() {
-        any_finished = true;
-        match ev.to {
-            PileType::Tableau(col) => {
-                game.tableau[col].extend(ev.cards.iter().cloned());
-                if ev.flip_final {
-                    if let Some(last) = game.tableau[col].last_mut() {
-                        last.face_up = true;
-                    }
-                }
-            }
-            PileType::Foundation(idx) => {
-                if let Some(card) = ev.cards.first() {
-                    if idx >= game.foundations.len() {
-                        game.foundations.push(*card);
-                    } else {
-                        game.foundations[idx] = *card;
-                    }
-                }
-                if ev.flip_final {
-                    game.score += 100;
-                    if let PileType::Tableau(from_col) = ev.from {
-                        if let Some(last) = game.tableau[from_col].last_mut() {
-                            last.face_up = true;
-                        }
-                        game.visual_pile_locks[from_col] = None;
-                    }
-                    if game.is_won() {
-                        next_state.set(GameState::Won);
-                    }
-                }
-            }
-            _ => {}
-        }
-    }
-    if any_finished {
-        check_request.pending = true;
-    }
-}
-
-pub fn auto_move_system(
-    mut game: ResMut<SpiderGame>,
-    time: Res<Time>,
-    mut next_state: ResMut<NextState<GameState>>,
-    _queue_res: ResMut<AnimationEventQueue>,
+    mut move_events: MessageReader<MoveEvent>,
     mut history: ResMut<GameHistory>,
-    mut check_request: ResMut<SequenceCheckRequest>,
+    mut queue_res: ResMut<AnimationEventQueue>,
+    pace: Res<AnimationPace>,
+    mut move_writer: MessageWriter<MoveAppliedEvent>,
+    mut suit_writer: MessageWriter<SuitCompletedEvent>,
+    mut start_writer: MessageWriter<StartAnimationEvent>,
 ) {
-    if let Some((target_pos, to_pile)) = game.auto_move_to {
-        if let Some(current_drag_pos) = game.drag_pos {
-            let speed = 25.0;
-            let delta = time.delta_secs() * speed;
-            let new_pos = current_drag_pos.lerp(target_pos, delta);
-            game.drag_pos = Some(new_pos);
-
-            if new_pos.distance(target_pos) < 2.0 {
-                let mut clean_state = (*game).clone();
-                clean_state.selected = None;
-                clean_state.drag_pos = None;
-                clean_state.auto_move_to = None;
-                history.stack.push(clean_state);
-
-                if let Some(selected) = game.selected.take() {
-                    let cards_to_move: Vec<Card> = match selected.from {
-                        PileType::Tableau(from_col) => {
-                            let drained: Vec<Card> =
-                                game.tableau[from_col].drain(selected.index..).collect();
-                            if let Some(new_top) = game.tableau[from_col].last_mut() {
-                                new_top.face_up = true;
-                            }
-                            drained
-                        }
-                        _ => vec![],
-                    };
-
-                    if let PileType::Tableau(col) = to_pile {
-                        game.tableau[col].extend(cards_to_move);
-                        if let Some(last) = game.tableau[col].last_mut() {
-                            last.face_up = true;
-                        }
-                    }
-
-                    game.move_count += 1;
-                    game.score -= 1;
-
-                    if game.score <= 0 {
-                        next_state.set(GameState::Lost);
-                    } else if game.is_won() {
-                        next_state.set(GameState::Won);
-                    }
-
-                    check_request.pending = true;
-
-                    if game.is_won() {
-                        next_state.set(GameState::Won);
-                    }
-                }
+    for ev in move_events.read() {
+        let mut snapshot = (*game).clone();
+        snapshot.selected = None;
+        snapshot.drag_pos = None;
+        snapshot.drag_offset = None;
 
-                game.auto_move_to = None;
-                game.drag_pos = None;
-                game.selected = None;
-            }
-        }
+        let Some(target_idx) = game.tableau.get(ev.to_col).map(Vec::len) else {
+            continue;
+        };
+        let Some(moved) = game.move_cards(ev.from_col, ev.index, ev.to_col) else {
+            continue;
+        };
+        history.stack.push(snapshot);
+
+        move_writer.write(MoveAppliedEvent {
+            from: PileType::Tableau(ev.from_col),
+            to: PileType::Tableau(ev.to_col),
+            card: moved.cards[0],
+            count: moved.cards.len(),
+            revealed: moved.revealed,
+        });
+        start_writer.write(StartAnimationEvent {
+            cards: moved.cards,
+            from: PileType::Tableau(ev.from_col),
+            to: PileType::Tableau(ev.to_col),
+            from_index: ev.index,
+            visual_start_pos: ev.drag_pos,
+            delay: 0.0,
+            original_pile_len: None,
+            target_index_override: Some(target_idx),
+            fly_z_override: None,
+        });
+
+        complete_runs(&mut game, &mut queue_res, &pace, &mut suit_writer);
     }
 }
 
-pub fn stability_check_system(
-    mut check_request: ResMut<SequenceCheckRequest>,
-    mut game: ResMut<SpiderGame>,
-    mut queue_res: ResMut<AnimationEventQueue>,
-    card_query: Query<(&ClickableCard, &Transform, &CardTarget)>,
+pub fn game_over_system(
+    game: Res<SpiderGame>,
+    queue_res: Res<AnimationEventQueue>,
+    moving_cards: Query<(), With<MovingCard>>,
+    mut next_state: ResMut<NextState<GameState>>,
 ) {
-    if !check_request.pending {
-        return;
-    }
-
-    let logical_count: usize = game.tableau.iter().map(|col| col.len()).sum();
-    let visual_count = card_query
-        .iter()
-        .filter(|(c, _, _)| matches!(c.pile_type, PileType::Tableau(_)))
-        .count();
-
-    if logical_count != visual_count {
+    if !queue_res.queue.is_empty() || !moving_cards.is_empty() {
         return;
     }
-
-    let mut all_stable = true;
-    let threshold = 1.0;
-
-    for (_, transform, target) in card_query.iter() {
-        if transform.translation.distance(target.translation) > threshold {
-            all_stable = false;
-            break;
-        }
-        if (transform.scale.x - target.scale).abs() > 0.01 {
-            all_stable = false;
-            break;
-        }
+    if game.is_won() {
+        next_state.set(GameState::Won);
+    } else if game.is_lost() {
+        next_state.set(GameState::Lost);
     }
+}
 
-    if all_stable {
-        check_completed_sequences(&mut game, &mut queue_res);
-        check_request.pending = false;
+pub fn game_clock_system(time: Res<Time>, mut game: ResMut<SpiderGame>) {
+    if !game.is_won() {
+        game.bypass_change_detection().elapsed_secs += time.delta_secs();
     }
 }
 
@@ -291,30 +285,63 @@ pub fn undo_system(
     mut history: ResMut<GameHistory>,
     mut undo_events: MessageReader<UndoEvent>,
     mut queue_res: ResMut<AnimationEventQueue>,
-    mut check_request: ResMut<SequenceCheckRequest>,
     moving_cards: Query<Entity, With<MovingCard>>,
 ) {
     for _ in undo_events.read() {
-        if game.auto_move_to.is_some() {
+        if !game.can_undo() {
             continue;
         }
         if let Some(prev_state) = history.stack.pop() {
-            let current_diff = game.difficulty;
-            *game = prev_state;
-            game.difficulty = current_diff;
-
-            game.visual_pile_locks = [None; 10];
+            game.undo_to(prev_state);
 
             queue_res.queue.clear();
             for entity in moving_cards.iter() {
                 commands.entity(entity).despawn();
             }
-            check_request.pending = false;
         }
     }
 }
 
-fn check_completed_sequences(game: &mut SpiderGame, queue_res: &mut ResMut<AnimationEventQueue>) {
+fn enqueue(queue_res: &mut AnimationEventQueue, mut events: Vec<StartAnimationEvent>) {
+    events.append(&mut queue_res.queue);
+    queue_res.queue = events;
+}
+
+fn complete_runs(
+    game: &mut SpiderGame,
+    queue_res: &mut AnimationEventQueue,
+    pace: &AnimationPace,
+    suit_writer: &mut MessageWriter<SuitCompletedEvent>,
+) {
+    let delay_step = pace.duration(CASCADE_DELAY);
+    for run in check_completed_sequences(game) {
+        let original_len = run.start + run.cards.len();
+        let mut events = Vec::new();
+        for (i, card) in run.cards.iter().rev().enumerate() {
+            events.push(StartAnimationEvent {
+                cards: vec![*card],
+                from: PileType::Tableau(run.column),
+                to: PileType::Foundation(run.foundation),
+                from_index: original_len - 1 - i,
+                visual_start_pos: None,
+                delay: i as f32 * delay_step,
+                original_pile_len: Some(original_len),
+                target_index_override: None,
+                fly_z_override: Some(i as f32),
+            });
+        }
+        events.reverse();
+        enqueue(queue_res, events);
+        suit_writer.write(SuitCompletedEvent {
+            suit: run.cards[0].suit,
+            foundation: run.foundation,
+            stock_remaining: game.stock.len(),
+        });
+    }
+}
+
+pub fn check_completed_sequences(game: &mut SpiderGame) -> Vec<CompletedRun> {
+    let mut runs = Vec::new();
     for col in 0..10 {
         let current_len = game.tableau[col].len();
         if current_len < 13 {
@@ -328,9 +355,10 @@ fn check_completed_sequences(game: &mut SpiderGame, queue_res: &mut ResMut<Anima
         }
 
         let suit = potential_sequence[0].suit;
-        let mut is_sequence = true;
+        let mut is_sequence = potential_sequence[12].face_up;
         for i in 0..12 {
-            if potential_sequence[i].suit != suit
+            if !potential_sequence[i].face_up
+                || potential_sequence[i].suit != suit
                 || potential_sequence[i + 1].suit != suit
                 || potential_sequence[i].rank.value() != potential_sequence[i + 1].rank.value() + 1
             {
@@ -340,30 +368,142 @@ fn check_completed_sequences(game: &mut SpiderGame, queue_res: &mut ResMut<Anima
         }
 
         if is_sequence {
-            game.visual_pile_locks[col] = Some(current_len);
-
-            let completed_cards: Vec<Card> = game.tableau[col].drain(start_idx..).collect();
-            let mut events = Vec::new();
-            let total_cards = completed_cards.len();
-
-            for (i, card) in completed_cards.iter().rev().enumerate() {
-                let delay = i as f32 * 0.15;
-                let is_bottom_card = i == total_cards - 1;
-                events.push(StartAnimationEvent {
-                    cards: vec![*card],
-                    from: PileType::Tableau(col),
-                    to: PileType::Foundation(game.foundations.len()),
-                    from_index: start_idx + (12 - i),
-                    visual_start_pos: None,
-                    delay,
-                    flip_final: is_bottom_card,
-                    original_pile_len: Some(current_len),
-                    target_index_override: None,
-                    fly_z_override: Some(i as f32),
-                });
+            let cards: Vec<Card> = game.tableau[col].drain(start_idx..).collect();
+            game.reveal_top(col);
+            game.foundations.push(cards[0]);
+            game.score += game.scoring.suit_bonus();
+            runs.push(CompletedRun {
+                column: col,
+                start: start_idx,
+                cards,
+                foundation: game.foundations.len() - 1,
+            });
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debug::check_model;
+
+    fn test_app(seed: u64) -> App {
+        let mut app = App::new();
+        app.add_message::<DealEvent>()
+            .add_message::<DealStockEvent>()
+            .add_message::<UndoEvent>()
+            .add_message::<MoveEvent>()
+            .add_message::<MoveAppliedEvent>()
+            .add_message::<SuitCompletedEvent>()
+            .add_message::<StartAnimationEvent>()
+            .init_resource::<Time>()
+            .init_resource::<AnimationPace>()
+            .init_resource::<AnimationEventQueue>()
+            .init_resource::<GameHistory>()
+            .init_resource::<SpiderGame>()
+            .init_resource::<Settings>()
+            .init_resource::<Statistics>()
+            .init_resource::<WinnableSeeds>()
+            .add_systems(
+                Update,
+                (
+                    deal_system,
+                    deal_stock_system,
+                    animation_event_dispatcher,
+                    move_cards_system
+                        .after(animation_event_dispatcher)
+                        .after(deal_stock_system),
+                ),
+            );
+        app.world_mut()
+            .write_message(DealEvent(Difficulty::Hard, GameMode::Seeded(seed)));
+        app.update();
+        app
+    }
+
+    fn game(app: &App) -> &SpiderGame {
+        app.world().resource::<SpiderGame>()
+    }
+
+    fn drag(app: &mut App, (from_col, index, to_col): (usize, usize, usize)) {
+        app.world_mut().write_message(MoveEvent {
+            from_col,
+            index,
+            to_col,
+            drag_pos: None,
+        });
+    }
+
+    #[test]
+    fn deal_then_drag_in_the_same_frame_keeps_every_card() {
+        for seed in 0..20 {
+            let mut app = test_app(seed);
+            let before = game(&app).legal_moves();
+            app.world_mut().write_message(DealStockEvent);
+            for &mv in before.iter().take(3) {
+                drag(&mut app, mv);
             }
-            events.reverse();
-            queue_res.queue.extend(events);
+            app.update();
+            check_model(game(&app)).unwrap();
+            assert_eq!(game(&app).stock.len(), 40);
+        }
+    }
+
+    #[test]
+    fn drag_while_dealt_cards_are_still_queued_keeps_every_card() {
+        let mut dragged = 0;
+        for seed in 0..50 {
+            let mut app = test_app(seed);
+            app.world_mut().write_message(DealStockEvent);
+            app.update();
+            assert!(!app
+                .world()
+                .resource::<AnimationEventQueue>()
+                .queue
+                .is_empty());
+            let Some(&mv) = game(&app).legal_moves().first() else {
+                continue;
+            };
+            let moved = game(&app).tableau[mv.0].len() - mv.1;
+            let target_len = game(&app).tableau[mv.2].len();
+            drag(&mut app, mv);
+            app.update();
+
+            let game = game(&app);
+            check_model(game).unwrap();
+            assert_eq!(game.tableau[mv.2].len(), target_len + moved);
+            assert_eq!(app.world().resource::<GameHistory>().stack.len(), 2);
+            dragged += 1;
         }
+        assert!(dragged > 0);
+    }
+
+    #[test]
+    fn repeated_deals_and_drags_keep_every_card() {
+        for seed in 0..10 {
+            let mut app = test_app(seed);
+            for step in 0..30 {
+                if step % 6 == 0 {
+                    app.world_mut().write_message(DealStockEvent);
+                }
+                if let Some(&mv) = game(&app).legal_moves().get(step) {
+                    drag(&mut app, mv);
+                }
+                app.update();
+                check_model(game(&app)).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn stale_drag_is_refused_instead_of_duplicating() {
+        let mut app = test_app(7);
+        let mv = game(&app).legal_moves()[0];
+        drag(&mut app, mv);
+        drag(&mut app, mv);
+        app.update();
+        check_model(game(&app)).unwrap();
+        assert_eq!(game(&app).move_count, 1);
     }
 }
@@ -0,0 +1,171 @@
+use std::fs;
+
+use bevy::asset::io::file::FileAssetReader;
+use bevy::asset::AssetPath;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::models::{Card, Rank, Suit};
+use crate::resources::GameAssets;
+use crate::settings::Settings;
+
+const ASSETS_DIR: &str = "assets";
+const THEMES_DIR: &str = "themes";
+const MANIFEST_FILE: &str = "theme.json";
+pub const DEFAULT_THEME: &str = "classic";
+
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Themes {
+            available: discover_themes(),
+        })
+        .add_systems(Update, apply_theme);
+    }
+}
+
+#[derive(Clone, Deserialize)]
+pub struct ThemeManifest {
+    pub name: String,
+    pub faces: String,
+    pub back: String,
+    pub slot: String,
+    pub font: String,
+    pub background: [u8; 3],
+}
+
+impl Default for ThemeManifest {
+    fn default() -> Self {
+        Self {
+            name: "Classic".to_string(),
+            faces: "/cards/{rank}{suit}.png".to_string(),
+            back: "/deck_black.png".to_string(),
+            slot: "/card_blank.png".to_string(),
+            font: "/pixeloid.sans.ttf".to_string(),
+            background: [93, 117, 87],
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct ThemeBackground;
+
+pub struct ThemeEntry {
+    pub id: String,
+    pub manifest: ThemeManifest,
+}
+
+#[derive(Resource)]
+pub struct Themes {
+    pub available: Vec<ThemeEntry>,
+}
+
+impl Themes {
+    pub fn get(&self, id: &str) -> &ThemeEntry {
+        self.available
+            .iter()
+            .find(|theme| theme.id == id)
+            .unwrap_or(&self.available[0])
+    }
+
+    pub fn next(&self, id: &str) -> String {
+        let index = self
+            .available
+            .iter()
+            .position(|theme| theme.id == id)
+            .map_or(0, |i| (i + 1) % self.available.len());
+        self.available[index].id.clone()
+    }
+}
+
+fn discover_themes() -> Vec<ThemeEntry> {
+    let dir = FileAssetReader::get_base_path()
+        .join(ASSETS_DIR)
+        .join(THEMES_DIR);
+    let mut themes: Vec<ThemeEntry> = fs::read_dir(&dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let id = entry.file_name().to_string_lossy().to_string();
+            let contents = fs::read_to_string(entry.path().join(MANIFEST_FILE)).ok()?;
+            match serde_json::from_str(&contents) {
+                Ok(manifest) => Some(ThemeEntry { id, manifest }),
+                Err(err) => {
+                    warn!("Failed to parse theme {}: {}", id, err);
+                    None
+                }
+            }
+        })
+        .collect();
+    themes.sort_by(|a, b| a.id.cmp(&b.id));
+
+    if !themes.iter().any(|theme| theme.id == DEFAULT_THEME) {
+        themes.insert(
+            0,
+            ThemeEntry {
+                id: DEFAULT_THEME.to_string(),
+                manifest: ThemeManifest::default(),
+            },
+        );
+    }
+    themes
+}
+
+fn resolve(theme_id: &str, path: &str) -> AssetPath<'static> {
+    let base = AssetPath::parse(&format!("{}/{}", THEMES_DIR, theme_id)).into_owned();
+    base.resolve(path).unwrap_or_else(|err| {
+        warn!("Invalid theme path {}: {}", path, err);
+        AssetPath::parse(path).into_owned()
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn apply_theme(
+    settings: Res<Settings>,
+    themes: Res<Themes>,
+    asset_server: Res<AssetServer>,
+    mut game_assets: ResMut<GameAssets>,
+    mut clear_color: ResMut<ClearColor>,
+    mut fonts: Query<&mut TextFont>,
+    mut backgrounds: Query<&mut BackgroundColor, With<ThemeBackground>>,
+    mut applied: Local<Option<(String, bool)>>,
+) {
+    let key = (settings.theme.clone(), settings.drawn_cards());
+    if applied.as_ref() == Some(&key) {
+        return;
+    }
+    let theme = themes.get(&settings.theme);
+    let manifest = &theme.manifest;
+
+    let old_font = game_assets.font.clone();
+
+    for suit in Suit::all() {
+        for rank in Rank::all() {
+            let path = Card::new(suit, rank).face_path(&manifest.faces);
+            game_assets
+                .faces
+                .insert((suit, rank), asset_server.load(resolve(&theme.id, &path)));
+        }
+    }
+    game_assets.back_texture = asset_server.load(resolve(&theme.id, &manifest.back));
+    game_assets.blank_texture = asset_server.load(resolve(&theme.id, &manifest.slot));
+    game_assets.font = asset_server.load(resolve(&theme.id, &manifest.font));
+    let [r, g, b] = manifest.background;
+    game_assets.background = Color::srgb_u8(r, g, b);
+    clear_color.0 = game_assets.background;
+
+    if applied.is_some() {
+        for mut text_font in fonts.iter_mut() {
+            if text_font.font == old_font {
+                text_font.font = game_assets.font.clone();
+            }
+        }
+        for mut background in backgrounds.iter_mut() {
+            background.0 = game_assets.background;
+        }
+    }
+
+    *applied = Some(key);
+}
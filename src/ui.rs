@@ -1,11 +1,20 @@
-use crate::layout::{get_pile_position, BASE_VERTICAL_OFFSET, CARD_HEIGHT, CARD_WIDTH};
-use crate::models::Card;
+use crate::animation::{AnimationPace, FLIGHT_SPEED, SETTLE_SPEED};
+use crate::atlas::CardAtlas;
+use crate::layout::{
+    get_pile_position, pile_shape, ColumnShape, BASE_VERTICAL_OFFSET, CARD_HEIGHT, CARD_WIDTH,
+};
+use crate::models::{Card, Rank, Suit};
 use crate::resources::{
-    AnimationEventQueue, Difficulty, GameAssets, GameSounds, GameState, PileType, SpiderGame,
-    StartAnimationEvent,
+    AnimationEventQueue, Difficulty, GameAssets, GameMode, GameState, Landing, PileType,
+    SpiderGame, StartAnimationEvent,
+};
+use crate::settings::Settings;
+use crate::systems::{
+    animation_event_dispatcher, move_cards_system, AnimationFinishedEvent, DealEvent,
 };
-use crate::systems::{animation_event_dispatcher, AnimationFinishedEvent, DealEvent};
+use crate::theme::ThemeBackground;
 use bevy::prelude::*;
+use bevy::window::WindowResized;
 use std::collections::HashMap;
 
 pub struct UiPlugin;
@@ -21,8 +30,11 @@ impl Plugin for UiPlugin {
             .add_systems(
                 Update,
                 (
-                    start_animation_system.after(animation_event_dispatcher),
+                    start_animation_system
+                        .after(animation_event_dispatcher)
+                        .after(move_cards_system),
                     smooth_movement_system,
+                    reflow_moving_cards,
                     update_hud_system,
                 ),
             )
@@ -31,27 +43,35 @@ impl Plugin for UiPlugin {
                 PostUpdate,
                 update_game_view.run_if(in_state(GameState::Playing)),
             )
-            .add_systems(OnEnter(GameState::Playing), setup_ui)
-            .add_systems(OnEnter(GameState::Won), setup_win_ui)
-            .add_systems(OnEnter(GameState::Lost), setup_loss_ui)
+            .add_systems(OnEnter(GameState::Playing), (cleanup_ui, setup_ui).chain())
             .add_systems(OnEnter(GameState::Menu), cleanup_ui);
     }
 }
 
 #[derive(Component)]
-struct TransientEntity;
-#[derive(Component)]
-struct ScoreText;
-#[derive(Component)]
-struct MovesText;
+struct TransientEntity(usize);
+#[derive(Component, Clone, Copy)]
+enum HudText {
+    Score,
+    Moves,
+    Undos,
+    Time,
+}
 #[derive(Component)]
 struct GameUI;
 #[derive(Component)]
 struct MenuEntity;
-#[derive(Component)]
-struct QuitButton;
-#[derive(Component)]
-struct DifficultyButton(Difficulty);
+#[derive(Component, Clone, Copy)]
+enum MenuButton {
+    Difficulty(Difficulty),
+    Daily,
+    Statistics,
+    Achievements,
+    Winnable,
+    Scoring,
+    Options,
+    Quit,
+}
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ClickableCard {
     pub pile_type: PileType,
@@ -65,7 +85,6 @@ pub struct ClickableEmptyPile {
 #[derive(Component)]
 pub struct MovingCard {
     pub cards: Vec<Card>,
-    pub from: PileType,
     pub to: PileType,
     pub start_pos: Vec3,
     pub end_pos: Vec3,
@@ -73,9 +92,19 @@ pub struct MovingCard {
     pub scale: f32,
     pub finished: bool,
     pub delay: f32,
-    pub flip_final: bool,
     pub flying_z: f32,
-    pub sound_played: bool,
+    pub target_index: usize,
+    pub window_size: Vec2,
+}
+
+impl MovingCard {
+    pub fn landing(&self) -> Landing {
+        Landing {
+            pile: self.to,
+            index: self.target_index,
+            count: self.cards.len(),
+        }
+    }
 }
 
 #[derive(Component)]
@@ -84,7 +113,55 @@ pub struct CardTarget {
     pub scale: f32,
 }
 
-fn setup_menu(mut commands: Commands, game_assets: Res<GameAssets>) {
+fn menu_button_label(button: MenuButton, settings: &Settings) -> String {
+    match button {
+        MenuButton::Difficulty(diff) => diff.label().to_string(),
+        MenuButton::Daily => "Daily Challenge".to_string(),
+        MenuButton::Statistics => "Statistics".to_string(),
+        MenuButton::Achievements => "Achievements".to_string(),
+        MenuButton::Winnable if settings.winnable_deals => "Winnable Deals: On".to_string(),
+        MenuButton::Winnable => "Winnable Deals: Off".to_string(),
+        MenuButton::Scoring => format!("Scoring: {}", settings.scoring.label()),
+        MenuButton::Options => "Options".to_string(),
+        MenuButton::Quit => "Quit".to_string(),
+    }
+}
+
+fn spawn_menu_button(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    button: MenuButton,
+    color: Color,
+    settings: &Settings,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(300.0),
+                height: Val::Px(60.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(color),
+            button,
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new(menu_button_label(button, settings)),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 25.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn setup_menu(mut commands: Commands, game_assets: Res<GameAssets>, settings: Res<Settings>) {
+    let font = game_assets.font.clone();
     commands
         .spawn((
             Node {
@@ -96,14 +173,15 @@ fn setup_menu(mut commands: Commands, game_assets: Res<GameAssets>) {
                 row_gap: Val::Px(20.0),
                 ..default()
             },
-            BackgroundColor(Color::srgb(93. / 255., 117. / 255., 87. / 255.)),
+            BackgroundColor(game_assets.background),
+            ThemeBackground,
             MenuEntity,
         ))
         .with_children(|parent| {
             parent.spawn((
                 Text::new("Spider Solitaire"),
                 TextFont {
-                    font: game_assets.font.clone(),
+                    font: font.clone(),
                     font_size: 100.0,
                     ..default()
                 },
@@ -111,71 +189,60 @@ fn setup_menu(mut commands: Commands, game_assets: Res<GameAssets>) {
             ));
             let difficulties = [
                 (
-                    "One Suit",
                     Difficulty::Easy,
                     Color::srgb(142. / 255., 146. / 255., 87. / 255.),
                 ),
                 (
-                    "Two Suits",
                     Difficulty::Medium,
                     Color::srgb(222. / 255., 154. / 255., 40. / 255.),
                 ),
                 (
-                    "Four Suits",
                     Difficulty::Hard,
                     Color::srgb(229. / 255., 93. / 255., 77. / 255.),
                 ),
             ];
-            for (label, diff, color) in difficulties {
+            for (diff, color) in difficulties {
+                spawn_menu_button(
+                    parent,
+                    &font,
+                    MenuButton::Difficulty(diff),
+                    color,
+                    &settings,
+                );
+            }
+            let info_color = Color::srgb(93. / 255., 140. / 255., 160. / 255.);
+            let option_color = Color::srgba(0.0, 0.0, 0.0, 0.3);
+            let rows = [
+                vec![
+                    (MenuButton::Daily, info_color),
+                    (MenuButton::Statistics, info_color),
+                    (MenuButton::Achievements, info_color),
+                ],
+                vec![
+                    (MenuButton::Winnable, option_color),
+                    (MenuButton::Scoring, option_color),
+                    (MenuButton::Options, option_color),
+                ],
+            ];
+            for row in rows {
                 parent
-                    .spawn((
-                        Button,
-                        Node {
-                            width: Val::Px(300.0),
-                            height: Val::Px(60.0),
-                            justify_content: JustifyContent::Center,
-                            align_items: AlignItems::Center,
-                            ..default()
-                        },
-                        BackgroundColor(color),
-                        DifficultyButton(diff),
-                    ))
-                    .with_children(|p| {
-                        p.spawn((
-                            Text::new(label),
-                            TextFont {
-                                font: game_assets.font.clone(),
-                                font_size: 25.0,
-                                ..default()
-                            },
-                            TextColor(Color::WHITE),
-                        ));
+                    .spawn(Node {
+                        column_gap: Val::Px(20.0),
+                        ..default()
+                    })
+                    .with_children(|row_parent| {
+                        for (button, color) in row {
+                            spawn_menu_button(row_parent, &font, button, color, &settings);
+                        }
                     });
             }
-            parent
-                .spawn((
-                    Button,
-                    Node {
-                        width: Val::Px(300.0),
-                        height: Val::Px(60.0),
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        ..default()
-                    },
-                    BackgroundColor(Color::srgb(0.4, 0.4, 0.4)),
-                    QuitButton,
-                ))
-                .with_children(|p| {
-                    p.spawn((
-                        Text::new("Quit"),
-                        TextFont {
-                            font: game_assets.font.clone(),
-                            font_size: 25.0,
-                            ..default()
-                        },
-                        TextColor(Color::WHITE),
-                    ));
-                });
+            spawn_menu_button(
+                parent,
+                &font,
+                MenuButton::Quit,
+                Color::srgb(0.4, 0.4, 0.4),
+                &settings,
+            );
         });
 }
 fn cleanup_menu(mut commands: Commands, query: Query<Entity, With<MenuEntity>>) {
@@ -188,26 +255,37 @@ fn cleanup_ui(mut commands: Commands, query: Query<Entity, With<GameUI>>) {
         commands.entity(entity).despawn();
     }
 }
-#[allow(clippy::type_complexity)]
 fn handle_menu_interaction(
     mut next_state: ResMut<NextState<GameState>>,
     mut app_exit: MessageWriter<bevy::app::AppExit>,
     mut deal_writer: MessageWriter<DealEvent>,
-    diff_query: Query<
-        (&Interaction, &DifficultyButton),
-        (Changed<Interaction>, With<DifficultyButton>),
-    >,
-    quit_query: Query<&Interaction, (Changed<Interaction>, With<QuitButton>)>,
+    mut settings: ResMut<Settings>,
+    button_query: Query<(&Interaction, &MenuButton, &Children), Changed<Interaction>>,
+    mut text_query: Query<&mut Text>,
 ) {
-    for (interaction, diff_btn) in diff_query.iter() {
-        if *interaction == Interaction::Pressed {
-            deal_writer.write(DealEvent(diff_btn.0));
-            next_state.set(GameState::Playing);
+    for (interaction, button, children) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
         }
-    }
-    for interaction in quit_query.iter() {
-        if *interaction == Interaction::Pressed {
-            app_exit.write(bevy::app::AppExit::Success);
+        match *button {
+            MenuButton::Difficulty(diff) => {
+                deal_writer.write(DealEvent(diff, GameMode::Classic));
+                next_state.set(GameState::Playing);
+            }
+            MenuButton::Daily => next_state.set(GameState::DailyCalendar),
+            MenuButton::Statistics => next_state.set(GameState::Statistics),
+            MenuButton::Achievements => next_state.set(GameState::Achievements),
+            MenuButton::Winnable => settings.winnable_deals = !settings.winnable_deals,
+            MenuButton::Scoring => settings.scoring = settings.scoring.next(),
+            MenuButton::Options => next_state.set(GameState::Options),
+            MenuButton::Quit => {
+                app_exit.write(bevy::app::AppExit::Success);
+            }
+        }
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                **text = menu_button_label(*button, &settings);
+            }
         }
     }
 }
@@ -219,119 +297,59 @@ fn setup_ui(mut commands: Commands, game_assets: Res<GameAssets>) {
                 bottom: Val::Px(20.0),
                 width: Val::Percent(100.0),
                 justify_content: JustifyContent::Center,
+                flex_wrap: FlexWrap::Wrap,
                 column_gap: Val::Px(50.0),
+                row_gap: Val::Px(8.0),
                 ..default()
             },
             GameUI,
         ))
         .with_children(|parent| {
-            parent.spawn((
-                Text::new("Score: 500"),
-                TextFont {
-                    font: game_assets.font.clone(),
-                    font_size: 30.0,
-                    ..default()
-                },
-                TextColor(Color::WHITE),
-                ScoreText,
-            ));
-            parent.spawn((
-                Text::new("Moves: 0"),
-                TextFont {
-                    font: game_assets.font.clone(),
-                    font_size: 30.0,
-                    ..default()
-                },
-                TextColor(Color::WHITE),
-                MovesText,
-            ));
-        });
-}
-fn setup_win_ui(mut commands: Commands, game_assets: Res<GameAssets>) {
-    commands
-        .spawn((
-            Node {
-                position_type: PositionType::Absolute,
-                width: Val::Percent(100.0),
-                height: Val::Percent(100.0),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                ..default()
-            },
-            GlobalZIndex(2000),
-            GameUI,
-        ))
-        .with_children(|parent| {
-            parent
-                .spawn((
-                    Node {
-                        padding: UiRect::all(Val::Px(20.0)),
+            for hud in [
+                HudText::Score,
+                HudText::Moves,
+                HudText::Undos,
+                HudText::Time,
+            ] {
+                parent.spawn((
+                    Text::new(""),
+                    TextFont {
+                        font: game_assets.font.clone(),
+                        font_size: 30.0,
                         ..default()
                     },
-                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
-                ))
-                .with_children(|p| {
-                    p.spawn((
-                        Text::new("YOU WIN!"),
-                        TextFont {
-                            font: game_assets.font.clone(),
-                            font_size: 120.0,
-                            ..default()
-                        },
-                        TextColor(Color::srgb(229. / 255., 93. / 255., 77. / 255.)),
-                    ));
-                });
+                    TextColor(Color::WHITE),
+                    hud,
+                ));
+            }
         });
 }
-
-fn setup_loss_ui(mut commands: Commands, game_assets: Res<GameAssets>) {
-    commands
-        .spawn((
-            Node {
-                position_type: PositionType::Absolute,
-                width: Val::Percent(100.0),
-                height: Val::Percent(100.0),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                ..default()
-            },
-            GlobalZIndex(2000),
-            GameUI,
-        ))
-        .with_children(|parent| {
-            parent
-                .spawn((
-                    Node {
-                        padding: UiRect::all(Val::Px(20.0)),
-                        ..default()
-                    },
-                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
-                ))
-                .with_children(|p| {
-                    p.spawn((
-                        Text::new("YOU LOSE!"),
-                        TextFont {
-                            font: game_assets.font.clone(),
-                            font_size: 120.0,
-                            ..default()
-                        },
-                        TextColor(Color::srgb(142. / 255., 146. / 255., 87. / 255.)),
-                    ));
-                });
-        });
+pub fn format_time(secs: f32) -> String {
+    let total = secs as u32;
+    format!("{}:{:02}", total / 60, total % 60)
 }
 
-fn update_hud_system(
-    game: Res<SpiderGame>,
-    mut q_score: Query<&mut Text, (With<ScoreText>, Without<MovesText>)>,
-    mut q_moves: Query<&mut Text, (With<MovesText>, Without<ScoreText>)>,
-) {
-    if game.is_changed() {
-        for mut text in q_score.iter_mut() {
-            **text = format!("Score: {}", game.score);
-        }
-        for mut text in q_moves.iter_mut() {
-            **text = format!("Moves: {}", game.move_count);
+fn update_hud_system(game: Res<SpiderGame>, mut query: Query<(&mut Text, &HudText)>) {
+    for (mut text, hud) in query.iter_mut() {
+        let label = match hud {
+            HudText::Score => format!("Score: {}", game.scoring.format_score(game.score)),
+            HudText::Moves => format!("Moves: {}", game.move_count),
+            HudText::Undos => match game.scoring.undo_limit() {
+                Some(limit) => format!("Undos: {}/{}", game.undo_count, limit),
+                None => format!("Undos: {}", game.undo_count),
+            },
+            HudText::Time => {
+                let time = format!("Time: {}", format_time(game.elapsed_secs));
+                match game.mode {
+                    GameMode::Classic | GameMode::Seeded(_) => time,
+                    GameMode::Daily(date) => {
+                        format!("Daily {}  {}", date.format("%Y-%m-%d"), time)
+                    }
+                }
+            }
+        };
+        if **text != label {
+            **text = label;
         }
     }
 }
@@ -342,7 +360,9 @@ fn start_animation_system(
     mut animation_events: MessageReader<StartAnimationEvent>,
     game: Res<SpiderGame>,
     _queue_res: Res<AnimationEventQueue>,
-    asset_server: Res<AssetServer>,
+    game_assets: Res<GameAssets>,
+    atlas: Res<CardAtlas>,
+    settings: Res<Settings>,
     window_query: Query<&Window>,
     moving_cards: Query<(&Transform, &MovingCard)>,
     card_query: Query<(&ClickableCard, &Transform)>,
@@ -351,6 +371,7 @@ fn start_animation_system(
         return;
     };
     let window_size = Vec2::new(window.width(), window.height());
+    let collapse = settings.collapse_long_columns;
 
     let mut current_z = 900.0;
     for (t, _) in moving_cards.iter() {
@@ -365,17 +386,17 @@ fn start_animation_system(
             .find(|(c, _)| c.pile_type == ev.from && c.index == ev.from_index)
             .map(|(_, t)| t.translation);
 
-        let start_pos_xy = if let Some(real_pos) = found_visual_pos {
-            Vec3::new(real_pos.x, real_pos.y, 0.0)
-        } else if let Some(drag_pos) = ev.visual_start_pos {
+        let start_pos_xy = if let Some(drag_pos) = ev.visual_start_pos {
             Vec3::new(drag_pos.x, drag_pos.y, 0.0)
+        } else if let Some(real_pos) = found_visual_pos {
+            Vec3::new(real_pos.x, real_pos.y, 0.0)
         } else {
             let total_cards_for_layout = ev.original_pile_len.unwrap_or_else(|| match ev.from {
                 PileType::Tableau(c) => game.tableau[c].len().max(ev.from_index + 1),
                 _ => 1,
             });
-            let (log_start, _) =
-                get_pile_position(ev.from, ev.from_index, window_size, total_cards_for_layout);
+            let shape = pile_shape(&game, ev.from, total_cards_for_layout, collapse);
+            let (log_start, _) = get_pile_position(ev.from, ev.from_index, window_size, shape);
             Vec3::new(log_start.x, log_start.y, 0.0)
         };
 
@@ -383,16 +404,13 @@ fn start_animation_system(
             PileType::Tableau(c) => game.tableau[c].len().max(ev.from_index + 1),
             _ => 1,
         };
-        let (_, scale) = get_pile_position(ev.from, ev.from_index, window_size, total_for_scale);
+        let from_shape = pile_shape(&game, ev.from, total_for_scale, collapse);
+        let (from_pos, scale) = get_pile_position(ev.from, ev.from_index, window_size, from_shape);
 
-        let start_pos_z = if found_visual_pos.is_some() {
-            let (p, _) = get_pile_position(ev.from, ev.from_index, window_size, total_for_scale);
-            p.z
-        } else if ev.visual_start_pos.is_some() {
+        let start_pos_z = if ev.visual_start_pos.is_some() {
             current_z
         } else {
-            let (p, _) = get_pile_position(ev.from, ev.from_index, window_size, total_for_scale);
-            p.z
+            from_pos.z
         };
 
         let mut actual_start_x = start_pos_xy.x;
@@ -418,8 +436,13 @@ fn start_animation_system(
             }
         };
 
-        let to_total = target_idx + 1;
-        let (end_pos_logic, _) = get_pile_position(ev.to, target_idx, window_size, to_total);
+        let to_shape = pile_shape(
+            &game,
+            ev.to,
+            landing_total(&game, ev.to, target_idx),
+            collapse,
+        );
+        let (end_pos_logic, _) = get_pile_position(ev.to, target_idx, window_size, to_shape);
 
         let flying_z = if let Some(z_override) = ev.fly_z_override {
             900.0 + z_override
@@ -440,7 +463,6 @@ fn start_animation_system(
                 Visibility::default(),
                 MovingCard {
                     cards: ev.cards.clone(),
-                    from: ev.from,
                     to: ev.to,
                     start_pos: actual_start_pos,
                     end_pos: actual_end_pos,
@@ -448,20 +470,15 @@ fn start_animation_system(
                     scale,
                     finished: false,
                     delay: ev.delay,
-                    flip_final: ev.flip_final,
                     flying_z,
-                    sound_played: false,
+                    target_index: target_idx,
+                    window_size,
                 },
             ))
             .with_children(|parent| {
                 for (i, card) in ev.cards.iter().enumerate() {
-                    let texture: Handle<Image> = asset_server.load(card.texture_path());
                     parent.spawn((
-                        Sprite {
-                            image: texture,
-                            custom_size: Some(Vec2::new(CARD_WIDTH, CARD_HEIGHT)),
-                            ..default()
-                        },
+                        atlas.card_sprite(card, &game_assets),
                         Transform {
                             translation: Vec3::new(
                                 0.0,
@@ -476,36 +493,39 @@ fn start_animation_system(
     }
 }
 
+fn landing_total(game: &SpiderGame, pile: PileType, index: usize) -> usize {
+    match pile {
+        PileType::Tableau(col) => game.tableau[col].len().max(index + 1),
+        _ => index + 1,
+    }
+}
+
 fn animate_moving_cards(
     mut commands: Commands,
     mut query: Query<(Entity, &mut Transform, &mut MovingCard)>,
     time: Res<Time>,
     mut finished_writer: MessageWriter<AnimationFinishedEvent>,
-    game_sounds: Option<Res<GameSounds>>,
+    pace: Res<AnimationPace>,
 ) {
-    let speed = 3.5;
+    let mut landed = 0;
     for (entity, mut transform, mut moving) in query.iter_mut() {
         if moving.finished {
             commands.entity(entity).despawn();
             continue;
         }
 
-        if moving.delay > 0.0 {
+        if moving.delay > 0.0 && !pace.instant() {
             moving.delay -= time.delta_secs();
             transform.translation = moving.start_pos;
             continue;
         }
 
-        if !moving.sound_played {
-            if let Some(sounds) = &game_sounds {
-                commands.spawn((AudioPlayer(sounds.deal.clone()), PlaybackSettings::DESPAWN));
-            }
-            moving.sound_played = true;
-        }
-
-        moving.progress += time.delta_secs() * speed;
-        let t = moving.progress.min(1.0);
-        let eased_t = 1.0 - (1.0 - t) * (1.0 - t);
+        moving.progress += if pace.instant() {
+            1.0
+        } else {
+            time.delta_secs() * pace.rate(FLIGHT_SPEED)
+        };
+        let eased_t = pace.easing.apply(moving.progress);
 
         let current_xy = moving.start_pos.xy().lerp(moving.end_pos.xy(), eased_t);
         let current_z = moving.flying_z;
@@ -514,70 +534,126 @@ fn animate_moving_cards(
         transform.scale = Vec3::splat(moving.scale);
 
         if moving.progress >= 1.0 {
-            finished_writer.write(AnimationFinishedEvent {
-                cards: moving.cards.clone(),
-                from: moving.from,
-                to: moving.to,
-                flip_final: moving.flip_final,
-            });
+            landed += 1;
             moving.finished = true;
         }
     }
+    finished_writer.write_batch((0..landed).map(|_| AnimationFinishedEvent));
 }
 
-fn smooth_movement_system(mut query: Query<(&mut Transform, &CardTarget)>, time: Res<Time>) {
-    let dt = time.delta_secs();
-    let speed = 8.0;
+fn reflow_moving_cards(
+    mut resize_events: MessageReader<WindowResized>,
+    window_query: Query<&Window>,
+    game: Res<SpiderGame>,
+    settings: Res<Settings>,
+    mut moving_cards: Query<(&mut Transform, &mut MovingCard)>,
+) {
+    if resize_events.read().count() == 0 {
+        return;
+    }
+    let Some(window) = window_query.iter().next() else {
+        return;
+    };
+    let window_size = Vec2::new(window.width(), window.height());
+    let collapse = settings.collapse_long_columns;
+
+    for (mut transform, mut moving) in moving_cards.iter_mut() {
+        let ratio = window_size / moving.window_size;
+        let total = landing_total(&game, moving.to, moving.target_index);
+        let shape = pile_shape(&game, moving.to, total, collapse);
+        let (end_pos, scale) =
+            get_pile_position(moving.to, moving.target_index, window_size, shape);
+        moving.start_pos = (moving.start_pos.xy() * ratio).extend(moving.start_pos.z);
+        moving.end_pos = Vec3::new(end_pos.x, end_pos.y, moving.end_pos.z);
+        moving.scale = scale;
+        moving.window_size = window_size;
+        transform.translation =
+            (transform.translation.xy() * ratio).extend(transform.translation.z);
+        transform.scale = Vec3::splat(scale);
+    }
+}
+
+fn smooth_movement_system(
+    mut query: Query<(&mut Transform, &CardTarget)>,
+    time: Res<Time>,
+    pace: Res<AnimationPace>,
+) {
+    let blend = pace.approach(SETTLE_SPEED, time.delta_secs());
 
     for (mut transform, target) in query.iter_mut() {
         let current_scale = transform.scale.x;
-        let new_scale = current_scale + (target.scale - current_scale) * speed * dt;
+        let new_scale = current_scale + (target.scale - current_scale) * blend;
         transform.scale = Vec3::splat(new_scale);
 
         let current_pos = transform.translation;
         let target_pos = target.translation;
 
-        let new_x = current_pos.x + (target_pos.x - current_pos.x) * speed * dt;
-        let new_y = current_pos.y + (target_pos.y - current_pos.y) * speed * dt;
+        let new_x = current_pos.x + (target_pos.x - current_pos.x) * blend;
+        let new_y = current_pos.y + (target_pos.y - current_pos.y) * blend;
 
         transform.translation = Vec3::new(new_x, new_y, target_pos.z);
     }
 }
 
-#[allow(clippy::too_many_arguments)]
+struct CardView {
+    card: Card,
+    translation: Vec3,
+    scale: f32,
+    spawn_at: Vec3,
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn update_game_view(
     mut commands: Commands,
     game: Res<SpiderGame>,
     queue_res: Res<AnimationEventQueue>,
-    _state: Res<State<GameState>>,
-    asset_server: Res<AssetServer>,
-    clickable_cards: Query<(Entity, &ClickableCard)>,
-    clickable_empty: Query<(Entity, &ClickableEmptyPile)>,
-    transients: Query<Entity, With<TransientEntity>>,
+    game_assets: Res<GameAssets>,
+    atlas: Res<CardAtlas>,
+    settings: Res<Settings>,
+    mut resize_events: MessageReader<WindowResized>,
+    mut landed_events: MessageReader<AnimationFinishedEvent>,
     window_query: Query<&Window>,
+    moving_cards: Query<&MovingCard>,
+    mut cards: Query<(
+        Entity,
+        &ClickableCard,
+        &mut Card,
+        &mut CardTarget,
+        &mut Sprite,
+    )>,
+    mut empties: Query<
+        (Entity, &ClickableEmptyPile, &mut CardTarget, &mut Sprite),
+        Without<ClickableCard>,
+    >,
+    mut transients: Query<
+        (
+            Entity,
+            &TransientEntity,
+            &mut Card,
+            &mut Transform,
+            &mut Sprite,
+        ),
+        (Without<ClickableCard>, Without<ClickableEmptyPile>),
+    >,
 ) {
-    for entity in transients.iter() {
-        commands.entity(entity).despawn();
+    let resized = resize_events.read().count() > 0;
+    let landed = landed_events.read().count() > 0;
+    let art_changed = game_assets.is_changed() || atlas.is_changed();
+    if !(resized
+        || landed
+        || art_changed
+        || game.is_changed()
+        || queue_res.is_changed()
+        || settings.is_changed())
+    {
+        return;
     }
 
     let Some(window) = window_query.iter().next() else {
         return;
     };
     let window_size = Vec2::new(window.width(), window.height());
-
-    let blank_texture: Handle<Image> = asset_server.load("card_blank.png");
-    let back_texture: Handle<Image> = asset_server.load("deck_black.png");
-    let slot_color = Color::srgba(1.0, 1.0, 1.0, 0.3);
-
-    let mut existing_cards: HashMap<(PileType, usize), Entity> = HashMap::new();
-    for (e, c) in clickable_cards.iter() {
-        existing_cards.insert((c.pile_type, c.index), e);
-    }
-
-    let mut existing_empty: HashMap<PileType, Entity> = HashMap::new();
-    for (e, c) in clickable_empty.iter() {
-        existing_empty.insert(c.pile_type, e);
-    }
+    let collapse = settings.collapse_long_columns;
 
     let is_being_dragged = |pile_type: PileType, index: usize| -> bool {
         if let Some(selected) = &game.selected {
@@ -591,8 +667,27 @@ fn update_game_view(
         false
     };
 
-    let (stock_pos, scale) = get_pile_position(PileType::Stock, 0, window_size, 1);
-    let scale_vec = Vec3::splat(scale);
+    let in_flight: Vec<Landing> = queue_res
+        .queue
+        .iter()
+        .map(StartAnimationEvent::landing)
+        .chain(
+            moving_cards
+                .iter()
+                .filter(|moving| !moving.finished)
+                .map(MovingCard::landing),
+        )
+        .collect();
+    let is_in_flight = |pile_type: PileType, index: usize| -> bool {
+        in_flight
+            .iter()
+            .any(|landing| landing.covers(pile_type, index))
+    };
+
+    let mut views: HashMap<(PileType, usize), CardView> = HashMap::new();
+
+    let (stock_pos, scale) =
+        get_pile_position(PileType::Stock, 0, window_size, ColumnShape::loose(1));
     let mut cards_in_stock = game.stock.len();
     cards_in_stock += queue_res
         .queue
@@ -610,238 +705,195 @@ fn update_game_view(
             let offset_x = offset_factor * 20.0 * scale;
             let z_offset = max_deals as f32 - i as f32;
             let pos = Vec3::new(stock_pos.x + offset_x, stock_pos.y, stock_pos.z + z_offset);
-            let key = (PileType::Stock, i);
-            if let Some(entity) = existing_cards.remove(&key) {
-                commands.entity(entity).insert(CardTarget {
+            views.insert(
+                (PileType::Stock, i),
+                CardView {
+                    card: Card::new(Suit::Spades, Rank::Ace),
                     translation: pos,
                     scale,
-                });
-            } else {
-                commands.spawn((
-                    Sprite {
-                        image: back_texture.clone(),
-                        custom_size: Some(Vec2::new(CARD_WIDTH, CARD_HEIGHT)),
-                        ..default()
-                    },
-                    Transform {
-                        translation: pos,
-                        scale: scale_vec,
-                        ..default()
-                    },
-                    ClickableCard {
-                        pile_type: PileType::Stock,
-                        index: i,
-                    },
-                    CardTarget {
-                        translation: pos,
-                        scale,
-                    },
-                ));
-            }
+                    spawn_at: pos,
+                },
+            );
         }
     }
 
     for (i, card) in game.foundations.iter().enumerate() {
         let pile = PileType::Foundation(i);
-        let idx = 0;
-        if !is_being_dragged(pile, idx) {
-            let (pos, scale) = get_pile_position(pile, idx, window_size, 1);
-            if let Some(entity) = existing_cards.remove(&(pile, idx)) {
-                commands.entity(entity).insert(CardTarget {
+        if !is_being_dragged(pile, 0) && !is_in_flight(pile, 0) {
+            let (pos, scale) = get_pile_position(pile, 0, window_size, ColumnShape::loose(1));
+            views.insert(
+                (pile, 0),
+                CardView {
+                    card: *card,
                     translation: pos,
                     scale,
-                });
-                let texture_path = card.texture_path();
-                commands.entity(entity).insert(Sprite {
-                    image: asset_server.load(texture_path),
-                    custom_size: Some(Vec2::new(CARD_WIDTH, CARD_HEIGHT)),
-                    ..default()
-                });
-            } else {
-                let texture_path = card.texture_path();
-                commands.spawn((
-                    Sprite {
-                        image: asset_server.load(texture_path),
-                        custom_size: Some(Vec2::new(CARD_WIDTH, CARD_HEIGHT)),
-                        ..default()
-                    },
-                    Transform {
-                        translation: pos,
-                        scale: Vec3::splat(scale),
-                        ..default()
-                    },
-                    ClickableCard {
-                        pile_type: pile,
-                        index: idx,
-                    },
-                    CardTarget {
-                        translation: pos,
-                        scale,
-                    },
-                ));
-            }
+                    spawn_at: pos,
+                },
+            );
         }
     }
 
+    let mut slots: HashMap<PileType, Vec3> = HashMap::new();
     for (i, col) in game.tableau.iter().enumerate() {
         let pile_type = PileType::Tableau(i);
-        let (base_pos, _) = get_pile_position(pile_type, 0, window_size, 1);
-
-        if let Some(entity) = existing_empty.remove(&pile_type) {
-            commands.entity(entity).insert(CardTarget {
-                translation: Vec3::new(base_pos.x, base_pos.y, 0.0),
-                scale,
-            });
-        } else {
-            commands.spawn((
-                Sprite {
-                    image: blank_texture.clone(),
-                    custom_size: Some(Vec2::new(CARD_WIDTH, CARD_HEIGHT)),
-                    color: slot_color,
-                    ..default()
-                },
-                Transform {
-                    translation: Vec3::new(base_pos.x, base_pos.y, 0.0),
-                    scale: scale_vec,
-                    ..default()
-                },
-                ClickableEmptyPile { pile_type },
-                CardTarget {
-                    translation: Vec3::new(base_pos.x, base_pos.y, 0.0),
-                    scale,
-                },
-            ));
-        }
-
-        let total_in_pile_visual = if let Some(locked_len) = game.visual_pile_locks[i] {
-            locked_len
-        } else {
-            col.len()
-        };
+        let (base_pos, _) = get_pile_position(pile_type, 0, window_size, ColumnShape::loose(1));
+        slots.insert(pile_type, Vec3::new(base_pos.x, base_pos.y, 0.0));
 
+        let total_in_pile_visual = queue_res
+            .queue
+            .iter()
+            .filter(|ev| ev.from == pile_type)
+            .filter_map(|ev| ev.original_pile_len)
+            .fold(col.len(), usize::max);
+        let shape = ColumnShape::of(col, total_in_pile_visual, collapse);
         for (card_idx, card) in col.iter().enumerate() {
-            if !is_being_dragged(pile_type, card_idx) {
+            if !is_being_dragged(pile_type, card_idx) && !is_in_flight(pile_type, card_idx) {
                 let (target_pos, scale) =
-                    get_pile_position(pile_type, card_idx, window_size, total_in_pile_visual);
-
-                if let Some(entity) = existing_cards.remove(&(pile_type, card_idx)) {
-                    commands.entity(entity).insert(CardTarget {
+                    get_pile_position(pile_type, card_idx, window_size, shape);
+                let loose_shape = ColumnShape::of(col, card_idx + 1, collapse);
+                let (loose_pos, _) =
+                    get_pile_position(pile_type, card_idx, window_size, loose_shape);
+                views.insert(
+                    (pile_type, card_idx),
+                    CardView {
+                        card: *card,
                         translation: target_pos,
                         scale,
-                    });
-                    let texture_path = if card.face_up {
-                        card.texture_path()
-                    } else {
-                        "deck_black.png".to_string()
-                    };
-                    commands.entity(entity).insert(Sprite {
-                        image: asset_server.load(texture_path),
-                        custom_size: Some(Vec2::new(CARD_WIDTH, CARD_HEIGHT)),
-                        ..default()
-                    });
-                } else {
-                    let (loose_pos, _) =
-                        get_pile_position(pile_type, card_idx, window_size, card_idx + 1);
-
-                    let texture_path = if card.face_up {
-                        card.texture_path()
-                    } else {
-                        "deck_black.png".to_string()
-                    };
-                    commands.spawn((
-                        Sprite {
-                            image: asset_server.load(texture_path),
-                            custom_size: Some(Vec2::new(CARD_WIDTH, CARD_HEIGHT)),
-                            ..default()
-                        },
-                        Transform {
-                            translation: loose_pos,
-                            scale: Vec3::splat(scale),
-                            ..default()
-                        },
-                        ClickableCard {
-                            pile_type,
-                            index: card_idx,
-                        },
-                        CardTarget {
-                            translation: target_pos,
-                            scale,
-                        },
-                    ));
-                }
+                        spawn_at: loose_pos,
+                    },
+                );
             }
         }
     }
 
-    for (_, entity) in existing_cards {
-        commands.entity(entity).despawn();
+    for (entity, clickable, mut card, mut target, mut sprite) in cards.iter_mut() {
+        let Some(view) = views.remove(&(clickable.pile_type, clickable.index)) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        if *card != view.card || art_changed {
+            *card = view.card;
+            *sprite = atlas.card_sprite(&view.card, &game_assets);
+        }
+        if target.translation != view.translation || target.scale != view.scale {
+            target.translation = view.translation;
+            target.scale = view.scale;
+        }
     }
-    for (_, entity) in existing_empty {
-        commands.entity(entity).despawn();
+    for ((pile_type, index), view) in views {
+        commands.spawn((
+            atlas.card_sprite(&view.card, &game_assets),
+            Transform {
+                translation: view.spawn_at,
+                scale: Vec3::splat(view.scale),
+                ..default()
+            },
+            ClickableCard { pile_type, index },
+            view.card,
+            CardTarget {
+                translation: view.translation,
+                scale: view.scale,
+            },
+        ));
     }
 
-    if let Some(selected) = &game.selected {
-        if let Some(drag_pos) = game.drag_pos {
-            let cards_to_render: Vec<(usize, &Card)> = match selected.from {
-                PileType::Tableau(col) => game.tableau[col]
-                    .iter()
-                    .enumerate()
-                    .skip(selected.index)
-                    .collect(),
-                _ => vec![],
-            };
-            for (i, (_, card)) in cards_to_render.iter().enumerate() {
-                let texture: Handle<Image> = asset_server.load(card.texture_path());
-                commands.spawn((
-                    Sprite {
-                        image: texture,
-                        custom_size: Some(Vec2::new(CARD_WIDTH, CARD_HEIGHT)),
-                        ..default()
-                    },
-                    Transform {
-                        translation: Vec3::new(
-                            drag_pos.x,
-                            drag_pos.y - (i as f32 * BASE_VERTICAL_OFFSET * scale),
-                            500.0 + i as f32,
-                        ),
-                        scale: scale_vec,
-                        ..default()
-                    },
-                    TransientEntity,
+    let slot_sprite = || Sprite {
+        image: game_assets.blank_texture.clone(),
+        custom_size: Some(Vec2::new(CARD_WIDTH, CARD_HEIGHT)),
+        color: Color::srgba(1.0, 1.0, 1.0, 0.3),
+        ..default()
+    };
+    for (entity, empty, mut target, mut sprite) in empties.iter_mut() {
+        let Some(translation) = slots.remove(&empty.pile_type) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        if art_changed {
+            *sprite = slot_sprite();
+        }
+        if target.translation != translation || target.scale != scale {
+            target.translation = translation;
+            target.scale = scale;
+        }
+    }
+    for (pile_type, translation) in slots {
+        commands.spawn((
+            slot_sprite(),
+            Transform {
+                translation,
+                scale: Vec3::splat(scale),
+                ..default()
+            },
+            ClickableEmptyPile { pile_type },
+            CardTarget { translation, scale },
+        ));
+    }
+
+    let mut overlays: Vec<(Card, Vec3)> = Vec::new();
+    if let (Some(selected), Some(drag_pos)) = (&game.selected, game.drag_pos) {
+        if let PileType::Tableau(col) = selected.from {
+            for (i, card) in game.tableau[col].iter().skip(selected.index).enumerate() {
+                overlays.push((
+                    *card,
+                    Vec3::new(
+                        drag_pos.x,
+                        drag_pos.y - (i as f32 * BASE_VERTICAL_OFFSET * scale),
+                        500.0 + i as f32,
+                    ),
                 ));
             }
         }
     }
-
     for ev in queue_res.queue.iter() {
         if let PileType::Tableau(col) = ev.from {
             let total_estimate = ev
                 .original_pile_len
                 .unwrap_or(ev.from_index + ev.cards.len());
-
+            let shape = ColumnShape::of(&game.tableau[col], total_estimate, collapse);
             for (i, card) in ev.cards.iter().enumerate() {
-                let pile_idx = ev.from_index + i;
+                if is_in_flight(ev.from, ev.from_index + i) {
+                    continue;
+                }
                 let (pos, _) = get_pile_position(
                     PileType::Tableau(col),
-                    pile_idx,
+                    ev.from_index + i,
                     window_size,
-                    total_estimate,
+                    shape,
                 );
-                let texture: Handle<Image> = asset_server.load(card.texture_path());
-                commands.spawn((
-                    Sprite {
-                        image: texture,
-                        custom_size: Some(Vec2::new(CARD_WIDTH, CARD_HEIGHT)),
-                        ..default()
-                    },
-                    Transform {
-                        translation: pos,
-                        scale: scale_vec,
-                        ..default()
-                    },
-                    TransientEntity,
-                ));
+                overlays.push((*card, pos));
             }
         }
     }
+
+    let mut present = vec![false; overlays.len()];
+    for (entity, slot, mut card, mut transform, mut sprite) in transients.iter_mut() {
+        let Some((view_card, translation)) = overlays.get(slot.0) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        present[slot.0] = true;
+        if *card != *view_card || art_changed {
+            *card = *view_card;
+            *sprite = atlas.card_sprite(view_card, &game_assets);
+        }
+        if transform.translation != *translation || transform.scale.x != scale {
+            transform.translation = *translation;
+            transform.scale = Vec3::splat(scale);
+        }
+    }
+    for (slot, (card, translation)) in overlays.into_iter().enumerate() {
+        if present[slot] {
+            continue;
+        }
+        commands.spawn((
+            atlas.card_sprite(&card, &game_assets),
+            Transform {
+                translation,
+                scale: Vec3::splat(scale),
+                ..default()
+            },
+            card,
+            TransientEntity(slot),
+        ));
+    }
 }
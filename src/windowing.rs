@@ -0,0 +1,172 @@
+use bevy::prelude::*;
+use bevy::window::{
+    MonitorSelection, PrimaryWindow, WindowMode, WindowMoved, WindowResized, WindowResolution,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+const WINDOW_FILE: &str = "window.json";
+const SAVE_DELAY_SECS: f32 = 0.5;
+
+pub struct WindowingPlugin(pub WindowPrefs);
+
+impl Plugin for WindowingPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.0.clone()).add_systems(
+            Update,
+            (
+                toggle_fullscreen,
+                apply_window_prefs,
+                track_window,
+                save_window_prefs,
+            )
+                .chain(),
+        );
+    }
+}
+
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowPrefs {
+    pub fullscreen: bool,
+    pub monitor: Option<usize>,
+    pub size: Option<[u32; 2]>,
+    pub position: Option<[i32; 2]>,
+}
+
+impl Default for WindowPrefs {
+    fn default() -> Self {
+        Self {
+            fullscreen: true,
+            monitor: None,
+            size: None,
+            position: None,
+        }
+    }
+}
+
+impl WindowPrefs {
+    pub fn load() -> Self {
+        storage::load(WINDOW_FILE)
+    }
+
+    fn monitor_selection(&self) -> MonitorSelection {
+        self.monitor
+            .map_or(MonitorSelection::Primary, MonitorSelection::Index)
+    }
+
+    fn window_mode(&self) -> WindowMode {
+        if self.fullscreen {
+            WindowMode::BorderlessFullscreen(self.monitor_selection())
+        } else {
+            WindowMode::Windowed
+        }
+    }
+
+    pub fn next_monitor(&self, monitor_count: usize) -> Option<usize> {
+        match self.monitor {
+            None if monitor_count > 1 => Some(0),
+            Some(index) if index + 1 < monitor_count => Some(index + 1),
+            _ => None,
+        }
+    }
+
+    pub fn monitor_label(&self) -> String {
+        match self.monitor {
+            Some(index) => format!("Monitor: {}", index + 1),
+            None => "Monitor: Primary".to_string(),
+        }
+    }
+}
+
+pub fn primary_window(prefs: &WindowPrefs) -> Window {
+    let mut window = Window {
+        title: "Spider Solitaire".to_string(),
+        mode: prefs.window_mode(),
+        position: match prefs.position {
+            Some([x, y]) => WindowPosition::At(IVec2::new(x, y)),
+            None => WindowPosition::Centered(prefs.monitor_selection()),
+        },
+        ..default()
+    };
+    if let Some([width, height]) = prefs.size {
+        window.resolution = WindowResolution::new(width, height);
+    }
+    window
+}
+
+fn toggle_fullscreen(keyboard: Res<ButtonInput<KeyCode>>, mut prefs: ResMut<WindowPrefs>) {
+    if keyboard.just_pressed(KeyCode::F11) {
+        prefs.fullscreen = !prefs.fullscreen;
+    }
+}
+
+fn apply_window_prefs(
+    prefs: Res<WindowPrefs>,
+    mut window: Query<&mut Window, With<PrimaryWindow>>,
+    mut applied_monitor: Local<Option<Option<usize>>>,
+) {
+    if !prefs.is_changed() {
+        return;
+    }
+    let Ok(mut window) = window.single_mut() else {
+        return;
+    };
+    let mode = prefs.window_mode();
+    if window.mode != mode {
+        window.mode = mode;
+        if let (WindowMode::Windowed, Some([width, height])) = (mode, prefs.size) {
+            window.resolution.set_physical_resolution(width, height);
+        }
+    }
+    let monitor_changed = applied_monitor.is_some_and(|monitor| monitor != prefs.monitor);
+    if monitor_changed && !prefs.fullscreen {
+        window.position = WindowPosition::Centered(prefs.monitor_selection());
+    }
+    *applied_monitor = Some(prefs.monitor);
+}
+
+fn track_window(
+    mut resized: MessageReader<WindowResized>,
+    mut moved: MessageReader<WindowMoved>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut prefs: ResMut<WindowPrefs>,
+) {
+    let was_resized = resized.read().count() > 0;
+    let position = moved.read().last().map(|ev| ev.position);
+    let Ok(window) = window.single() else {
+        return;
+    };
+    if window.mode != WindowMode::Windowed {
+        return;
+    }
+    if was_resized {
+        let size = Some([
+            window.resolution.physical_width(),
+            window.resolution.physical_height(),
+        ]);
+        if prefs.size != size {
+            prefs.size = size;
+        }
+    }
+    if let Some(position) = position {
+        let position = Some([position.x, position.y]);
+        if prefs.position != position {
+            prefs.position = position;
+        }
+    }
+}
+
+fn save_window_prefs(time: Res<Time>, prefs: Res<WindowPrefs>, mut pending: Local<Option<Timer>>) {
+    if prefs.is_changed() && !prefs.is_added() {
+        *pending = Some(Timer::from_seconds(SAVE_DELAY_SECS, TimerMode::Once));
+    }
+    let Some(timer) = pending.as_mut() else {
+        return;
+    };
+    if timer.tick(time.delta()).just_finished() {
+        storage::save(WINDOW_FILE, &*prefs);
+        *pending = None;
+    }
+}
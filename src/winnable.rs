@@ -0,0 +1,228 @@
+use bevy::prelude::*;
+use bevy::tasks::futures::check_ready;
+use bevy::tasks::{AsyncComputeTaskPool, Task};
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::resources::Difficulty;
+use crate::settings::Settings;
+use crate::solver::is_winnable;
+use crate::storage;
+
+pub const WINNABLE_SEEDS_FILE: &str = "winnable_seeds.json";
+const POOL_TARGET: usize = 8;
+const SOLVER_NODES: usize = 100_000;
+
+const EASY_SEEDS: [u64; 32] = [
+    0x2F2140C10A370515,
+    0x31BD6D84E86CA2A0,
+    0xA979193B02469D96,
+    0x4FAD912095FC30DE,
+    0xC96B65F9CA03D1D7,
+    0x6B27B3BDBC95F75A,
+    0x2595026D34B38517,
+    0xC76A869E11091D8A,
+    0xEE6FAECF87AFD648,
+    0x58016E81E252E531,
+    0x1DB70343E71F762C,
+    0x93118C10FBE60F6D,
+    0xCD85491275A7891B,
+    0x68A4D6CC7D2A1D4A,
+    0x0097CF03C3AFCCA3,
+    0xBC48E4B7DAEB495E,
+    0x410A841ED8A6161F,
+    0x918B94B9C44F8267,
+    0xBBCFB1D33140E605,
+    0x04A2901AAF590674,
+    0x404CE2F6F4748F3B,
+    0xCCC20691E77E6C68,
+    0xCB270724B0E791EE,
+    0xD88AF0D597D278C4,
+    0xAF75E3AAAB72FC6D,
+    0x30BA6B9D5DDD73F2,
+    0x792859243F40DE3A,
+    0x503D4B08FBB4A78D,
+    0xBCE9BE6A1DCBF3D9,
+    0xBF0309557DA3F95A,
+    0xEF863C7080D8353B,
+    0x4234E36068B4EA1B,
+];
+
+const MEDIUM_SEEDS: [u64; 32] = [
+    0x38211D7BB093D3B6,
+    0xD14F10436CB53F5B,
+    0x71E5EF79172BF55C,
+    0x983FC7C8B1871789,
+    0x1065654FD621C713,
+    0x810199A249194D31,
+    0x81E6BCB36DB58EC7,
+    0x199971FDF0804F47,
+    0x8C227E9ADDC44213,
+    0xEC9560DC769AF254,
+    0x6DD585359CE77C7D,
+    0x72401930EC8532ED,
+    0x724E2AE8D3394E52,
+    0xAC69AA5EC5B88BCA,
+    0xD846E6CDE6842364,
+    0xF29612DF24DC7049,
+    0x95030B3EE56CDEBC,
+    0xAF51024DD8084DA4,
+    0x4D3BB241A502D35F,
+    0xF13C8D0F3611F42E,
+    0xF83723CEEF657833,
+    0x938C99B34256E380,
+    0x16D0429A62B8230D,
+    0x6F497DB1472DD851,
+    0x4533490F4897183A,
+    0x8A950A1D8E4B8DB2,
+    0xE58CF518770B5F85,
+    0x163AB190D538BEE1,
+    0xB958E50F32FD3B35,
+    0x46C502C2E4D352AF,
+    0xF0409B139B4BF17C,
+    0xF61CC50E5120428A,
+];
+
+const HARD_SEEDS: [u64; 24] = [
+    0xBBE16B8D555CAC3A,
+    0x5D12EAD1985D4360,
+    0x625584F9ACF76476,
+    0x59C82DFC7A6E76EF,
+    0x9DF9DE481CED0E6A,
+    0xAFCD1B41B03BA583,
+    0x93BD90A5830A30E8,
+    0xE112F0F620057330,
+    0x6843606FED236D68,
+    0xABFC48A76AC86616,
+    0xA95FC1D0F7E67C08,
+    0xA8A324FF148F9B7C,
+    0x6ABCE8022B59834D,
+    0x05CDAABF5742677E,
+    0x2A67F0F0F3BEEAB8,
+    0x969D30C4A0F1F4D7,
+    0xD3841A8170E7EA64,
+    0x0C9AE99DFAACD1B4,
+    0xE8C061F9BBF799A5,
+    0xB0FBB4AEBDC35CF4,
+    0x6FC4CA069EDBECD0,
+    0x921825F7A9ED3383,
+    0xC5F765406804308B,
+    0x7D498F7881D5E528,
+];
+
+pub struct WinnablePlugin;
+
+impl Plugin for WinnablePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(storage::load::<WinnableSeeds>(WINNABLE_SEEDS_FILE))
+            .init_resource::<SeedSearch>()
+            .add_systems(Update, (search_winnable_seeds, save_winnable_seeds));
+    }
+}
+
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WinnableSeeds {
+    easy: Vec<u64>,
+    medium: Vec<u64>,
+    hard: Vec<u64>,
+}
+
+impl WinnableSeeds {
+    fn pool(&self, difficulty: Difficulty) -> &Vec<u64> {
+        match difficulty {
+            Difficulty::Easy => &self.easy,
+            Difficulty::Medium => &self.medium,
+            Difficulty::Hard => &self.hard,
+        }
+    }
+
+    fn pool_mut(&mut self, difficulty: Difficulty) -> &mut Vec<u64> {
+        match difficulty {
+            Difficulty::Easy => &mut self.easy,
+            Difficulty::Medium => &mut self.medium,
+            Difficulty::Hard => &mut self.hard,
+        }
+    }
+
+    pub fn take(&mut self, difficulty: Difficulty) -> u64 {
+        if let Some(seed) = self.pool_mut(difficulty).pop() {
+            return seed;
+        }
+        let bundled: &[u64] = match difficulty {
+            Difficulty::Easy => &EASY_SEEDS,
+            Difficulty::Medium => &MEDIUM_SEEDS,
+            Difficulty::Hard => &HARD_SEEDS,
+        };
+        *bundled.choose(&mut rand::thread_rng()).unwrap()
+    }
+}
+
+#[derive(Resource, Default)]
+struct SeedSearch {
+    task: Option<Task<(Difficulty, u64, bool)>>,
+}
+
+fn search_winnable_seeds(
+    mut search: ResMut<SeedSearch>,
+    mut seeds: ResMut<WinnableSeeds>,
+    settings: Res<Settings>,
+) {
+    if let Some(task) = &mut search.task {
+        if let Some((difficulty, seed, winnable)) = check_ready(task) {
+            if winnable {
+                seeds.pool_mut(difficulty).push(seed);
+            }
+            search.task = None;
+        }
+        return;
+    }
+
+    if !settings.winnable_deals {
+        return;
+    }
+    let Some(difficulty) = Difficulty::all()
+        .into_iter()
+        .filter(|d| seeds.pool(*d).len() < POOL_TARGET)
+        .min_by_key(|d| seeds.pool(*d).len())
+    else {
+        return;
+    };
+
+    let seed: u64 = rand::random();
+    search.task = Some(AsyncComputeTaskPool::get().spawn(async move {
+        (
+            difficulty,
+            seed,
+            is_winnable(difficulty, seed, SOLVER_NODES),
+        )
+    }));
+}
+
+fn save_winnable_seeds(seeds: Res<WinnableSeeds>) {
+    if seeds.is_changed() && !seeds.is_added() {
+        storage::save(WINNABLE_SEEDS_FILE, &*seeds);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore = "runs the solver on every bundled seed; use --release"]
+    fn bundled_seeds_are_winnable() {
+        let bundled: [(Difficulty, &[u64]); 3] = [
+            (Difficulty::Easy, &EASY_SEEDS),
+            (Difficulty::Medium, &MEDIUM_SEEDS),
+            (Difficulty::Hard, &HARD_SEEDS),
+        ];
+        let failed: Vec<String> = bundled
+            .into_iter()
+            .flat_map(|(difficulty, seeds)| seeds.iter().map(move |&seed| (difficulty, seed)))
+            .filter(|&(difficulty, seed)| !is_winnable(difficulty, seed, SOLVER_NODES))
+            .map(|(difficulty, seed)| format!("{:?} {:#018X}", difficulty, seed))
+            .collect();
+        assert!(failed.is_empty(), "not winnable: {}", failed.join(", "));
+    }
+}
@@ -0,0 +1,311 @@
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use bevy::camera::{CameraProjection, RenderTargetInfo};
+use bevy::input::keyboard::{Key, KeyboardInput, NativeKey};
+use bevy::input::mouse::MouseButtonInput;
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::{ExitCondition, PrimaryWindow};
+
+use learn_rust::animation::AnimationPlugin;
+use learn_rust::atlas::CardAtlas;
+use learn_rust::debug::{model_violations, view_violations, DebugPlugin};
+use learn_rust::input::InputPlugin;
+use learn_rust::layout::{get_pile_position, pile_shape, CARD_HEIGHT};
+use learn_rust::models::Card;
+use learn_rust::resources::{
+    AnimationEventQueue, Difficulty, GameHistory, GameMode, GameState, PileType, SpiderGame,
+};
+use learn_rust::settings::Settings;
+use learn_rust::sound::PlaySound;
+use learn_rust::stats::Statistics;
+use learn_rust::systems::{DealEvent, UndoEvent};
+use learn_rust::ui::{CardTarget, ClickableCard, ClickableEmptyPile, MovingCard, UiPlugin};
+use learn_rust::winnable::WinnableSeeds;
+use learn_rust::SpiderPlugin;
+
+pub const WINDOW_WIDTH: u32 = 1280;
+pub const WINDOW_HEIGHT: u32 = 800;
+pub const FRAME: Duration = Duration::from_millis(16);
+const MAX_SETTLE_FRAMES: usize = 2000;
+
+pub struct Harness {
+    pub app: App,
+    window: Entity,
+}
+
+impl Harness {
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(StatesPlugin)
+            .add_plugins(bevy::input::InputPlugin)
+            .add_plugins(WindowPlugin {
+                primary_window: Some(Window {
+                    resolution: (WINDOW_WIDTH, WINDOW_HEIGHT).into(),
+                    ..default()
+                }),
+                exit_condition: ExitCondition::DontExit,
+                close_when_requested: false,
+                ..default()
+            })
+            .add_plugins(SpiderPlugin)
+            .add_plugins(AnimationPlugin)
+            .add_plugins(UiPlugin)
+            .add_plugins(InputPlugin)
+            .add_plugins(DebugPlugin)
+            .add_message::<PlaySound>()
+            .init_resource::<CardAtlas>()
+            .init_resource::<Settings>()
+            .init_resource::<Statistics>()
+            .init_resource::<WinnableSeeds>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+        app.update();
+
+        let window = app
+            .world_mut()
+            .query_filtered::<Entity, With<PrimaryWindow>>()
+            .single(app.world())
+            .unwrap();
+        let mut projection = OrthographicProjection::default_2d();
+        projection.update(WINDOW_WIDTH as f32, WINDOW_HEIGHT as f32);
+        let mut camera = Camera::default();
+        camera.computed.target_info = Some(RenderTargetInfo {
+            physical_size: UVec2::new(WINDOW_WIDTH, WINDOW_HEIGHT),
+            scale_factor: 1.0,
+        });
+        camera.computed.clip_from_view = projection.get_clip_from_view();
+        app.world_mut()
+            .spawn((Camera2d, camera, GlobalTransform::default()));
+        Harness { app, window }
+    }
+
+    pub fn frames(&mut self, count: usize) {
+        for _ in 0..count {
+            self.app.update();
+        }
+    }
+
+    pub fn animating(&mut self) -> bool {
+        let queued = !self
+            .app
+            .world()
+            .resource::<AnimationEventQueue>()
+            .queue
+            .is_empty();
+        let moving = self
+            .app
+            .world_mut()
+            .query::<&MovingCard>()
+            .iter(self.app.world())
+            .next()
+            .is_some();
+        queued || moving
+    }
+
+    pub fn settle(&mut self) {
+        for _ in 0..MAX_SETTLE_FRAMES {
+            self.app.update();
+            if !self.animating() {
+                self.frames(2);
+                if !self.animating() {
+                    return;
+                }
+            }
+        }
+        panic!("animations did not finish in {} frames", MAX_SETTLE_FRAMES);
+    }
+
+    pub fn deal(&mut self, difficulty: Difficulty, seed: u64) {
+        self.start_deal(difficulty, seed);
+        self.settle();
+    }
+
+    pub fn start_deal(&mut self, difficulty: Difficulty, seed: u64) {
+        self.app
+            .world_mut()
+            .write_message(DealEvent(difficulty, GameMode::Seeded(seed)));
+        self.app
+            .world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+        self.app.update();
+    }
+
+    pub fn game(&self) -> &SpiderGame {
+        self.app.world().resource::<SpiderGame>()
+    }
+
+    pub fn history_len(&self) -> usize {
+        self.app.world().resource::<GameHistory>().stack.len()
+    }
+
+    pub fn state(&self) -> GameState {
+        *self.app.world().resource::<State<GameState>>().get()
+    }
+
+    pub fn shown_cards(&mut self) -> Vec<(PileType, usize, Card)> {
+        self.app
+            .world_mut()
+            .query::<(&ClickableCard, &Card)>()
+            .iter(self.app.world())
+            .map(|(clickable, card)| (clickable.pile_type, clickable.index, *card))
+            .collect()
+    }
+
+    pub fn assert_consistent(&mut self) {
+        let shown = self.shown_cards();
+        let mut violations = model_violations(self.game());
+        violations.extend(view_violations(self.game(), &[], &shown));
+        assert!(violations.is_empty(), "{}", violations.join("\n"));
+    }
+
+    fn card_target(&mut self, pile: PileType, index: usize) -> (Vec3, f32) {
+        self.app
+            .world_mut()
+            .query::<(&ClickableCard, &CardTarget)>()
+            .iter(self.app.world())
+            .find(|(clickable, _)| clickable.pile_type == pile && clickable.index == index)
+            .map(|(_, target)| (target.translation, target.scale))
+            .unwrap_or_else(|| panic!("no card drawn at {:?} #{}", pile, index))
+    }
+
+    pub fn card_grip(&mut self, pile: PileType, index: usize) -> Vec2 {
+        let (translation, scale) = self.card_target(pile, index);
+        translation.truncate() + Vec2::new(0.0, CARD_HEIGHT * scale / 2.0 - 4.0)
+    }
+
+    fn window_size(&self) -> Vec2 {
+        Vec2::new(WINDOW_WIDTH as f32, WINDOW_HEIGHT as f32)
+    }
+
+    fn landed_position(&self, column: usize, index: usize) -> (Vec3, f32) {
+        let game = self.game();
+        let pile = PileType::Tableau(column);
+        let collapse = self
+            .app
+            .world()
+            .resource::<Settings>()
+            .collapse_long_columns;
+        let shape = pile_shape(game, pile, game.tableau[column].len(), collapse);
+        get_pile_position(pile, index, self.window_size(), shape)
+    }
+
+    pub fn landed_grip(&self, column: usize, index: usize) -> Vec2 {
+        let (translation, scale) = self.landed_position(column, index);
+        translation.truncate() + Vec2::new(0.0, CARD_HEIGHT * scale / 2.0 - 4.0)
+    }
+
+    pub fn landed_drop_point(&self, column: usize) -> Vec2 {
+        let index = self.game().tableau[column].len().saturating_sub(1);
+        self.landed_position(column, index).0.truncate()
+    }
+
+    pub fn drop_point(&mut self, column: usize) -> Vec2 {
+        let pile = PileType::Tableau(column);
+        match self.game().tableau[column].len() {
+            0 => self
+                .app
+                .world_mut()
+                .query::<(&ClickableEmptyPile, &CardTarget)>()
+                .iter(self.app.world())
+                .find(|(empty, _)| empty.pile_type == pile)
+                .map(|(_, target)| target.translation.truncate())
+                .unwrap(),
+            len => self.card_target(pile, len - 1).0.truncate(),
+        }
+    }
+
+    pub fn stock_point(&mut self) -> Vec2 {
+        let mut stock: Vec<Vec3> = self
+            .app
+            .world_mut()
+            .query::<(&ClickableCard, &CardTarget)>()
+            .iter(self.app.world())
+            .filter(|(clickable, _)| clickable.pile_type == PileType::Stock)
+            .map(|(_, target)| target.translation)
+            .collect();
+        stock.sort_by(|a, b| b.z.total_cmp(&a.z));
+        stock.first().expect("stock is empty").truncate()
+    }
+
+    fn viewport_position(&mut self, world: Vec2) -> Vec2 {
+        let (camera, transform) = self
+            .app
+            .world_mut()
+            .query::<(&Camera, &GlobalTransform)>()
+            .single(self.app.world())
+            .unwrap();
+        camera
+            .world_to_viewport(transform, world.extend(0.0))
+            .unwrap()
+    }
+
+    pub fn move_mouse(&mut self, world: Vec2) {
+        let position = self.viewport_position(world);
+        self.app
+            .world_mut()
+            .get_mut::<Window>(self.window)
+            .unwrap()
+            .set_cursor_position(Some(position));
+    }
+
+    fn mouse_button(&mut self, state: ButtonState) {
+        let window = self.window;
+        self.app.world_mut().write_message(MouseButtonInput {
+            button: MouseButton::Left,
+            state,
+            window,
+        });
+    }
+
+    pub fn press(&mut self, world: Vec2) {
+        self.move_mouse(world);
+        self.mouse_button(ButtonState::Pressed);
+        self.app.update();
+    }
+
+    pub fn release(&mut self, world: Vec2) {
+        self.move_mouse(world);
+        self.mouse_button(ButtonState::Released);
+        self.app.update();
+    }
+
+    pub fn drag(&mut self, from: Vec2, to: Vec2) {
+        self.press(from);
+        for step in 1..=4 {
+            self.move_mouse(from.lerp(to, step as f32 / 4.0));
+            self.app.update();
+        }
+        self.release(to);
+    }
+
+    pub fn click(&mut self, world: Vec2) {
+        self.press(world);
+        self.release(world);
+    }
+
+    pub fn key(&mut self, key_code: KeyCode) {
+        let window = self.window;
+        for state in [ButtonState::Pressed, ButtonState::Released] {
+            self.app.world_mut().write_message(KeyboardInput {
+                key_code,
+                logical_key: Key::Unidentified(NativeKey::Unidentified),
+                state,
+                text: None,
+                repeat: false,
+                window,
+            });
+            self.app.update();
+        }
+    }
+
+    pub fn undo(&mut self) {
+        self.app.world_mut().write_message(UndoEvent);
+        self.app.update();
+    }
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use bevy::prelude::*;
+use common::Harness;
+use learn_rust::resources::{Difficulty, GameState, PileType};
+
+fn dealt(seed: u64) -> Harness {
+    let mut harness = Harness::new();
+    harness.deal(Difficulty::Hard, seed);
+    harness
+}
+
+fn first_move(harness: &Harness) -> Option<(usize, usize, usize)> {
+    harness.game().legal_moves().first().copied()
+}
+
+#[test]
+fn deal_draws_every_tableau_card_once() {
+    let mut harness = dealt(1);
+    assert_eq!(harness.state(), GameState::Playing);
+    let game = harness.game();
+    assert_eq!(game.stock.len(), 50);
+    assert_eq!(game.tableau.iter().map(Vec::len).sum::<usize>(), 54);
+    assert!(game
+        .tableau
+        .iter()
+        .all(|column| column.last().is_some_and(|card| card.face_up)));
+    harness.assert_consistent();
+    let tableau_entities = harness
+        .shown_cards()
+        .iter()
+        .filter(|(pile, _, _)| matches!(pile, PileType::Tableau(_)))
+        .count();
+    assert_eq!(tableau_entities, 54);
+}
+
+#[test]
+fn same_seed_deals_the_same_board() {
+    let a = dealt(42);
+    let b = dealt(42);
+    assert_eq!(a.game().tableau, b.game().tableau);
+    assert_eq!(a.game().stock, b.game().stock);
+}
+
+#[test]
+fn clicking_the_stock_deals_a_row() {
+    let mut harness = dealt(2);
+    let before: Vec<usize> = harness.game().tableau.iter().map(Vec::len).collect();
+    let stock = harness.stock_point();
+    harness.click(stock);
+    harness.settle();
+
+    let game = harness.game();
+    assert_eq!(game.stock.len(), 40);
+    for (column, len) in before.iter().enumerate() {
+        assert_eq!(game.tableau[column].len(), len + 1);
+    }
+    assert_eq!(harness.history_len(), 1);
+    harness.assert_consistent();
+}
+
+#[test]
+fn dragging_a_run_moves_it_and_undo_puts_it_back() {
+    let mut harness = dealt(3);
+    let Some((from, index, to)) = first_move(&harness) else {
+        panic!("seed has no opening move");
+    };
+    let before = harness.game().clone();
+    let grip = harness.card_grip(PileType::Tableau(from), index);
+    let target = harness.drop_point(to);
+    harness.drag(grip, target);
+    harness.settle();
+
+    let moved = before.tableau[from].len() - index;
+    let game = harness.game();
+    assert_eq!(game.tableau[from].len(), index);
+    assert_eq!(game.tableau[to].len(), before.tableau[to].len() + moved);
+    assert_eq!(game.move_count, 1);
+    assert!(game.selected.is_none());
+    harness.assert_consistent();
+
+    harness.key(KeyCode::KeyZ);
+    harness.settle();
+    assert_eq!(harness.game().tableau, before.tableau);
+    assert_eq!(harness.game().undo_count, 1);
+    harness.assert_consistent();
+}
+
+#[test]
+fn dropping_on_a_wrong_rank_changes_nothing() {
+    let mut harness = dealt(4);
+    let game = harness.game().clone();
+    let illegal = (0..10)
+        .flat_map(|from| (0..10).map(move |to| (from, to)))
+        .find(|&(from, to)| {
+            let index = game.tableau[from].len() - 1;
+            from != to && !game.can_move(from, index, to)
+        });
+    let Some((from, to)) = illegal else {
+        panic!("every top card fits everywhere");
+    };
+    let grip = harness.card_grip(PileType::Tableau(from), game.tableau[from].len() - 1);
+    let target = harness.drop_point(to);
+    harness.drag(grip, target);
+    harness.settle();
+
+    assert_eq!(harness.game().tableau, game.tableau);
+    assert_eq!(harness.history_len(), 0);
+    harness.assert_consistent();
+}
+
+#[test]
+fn drag_made_while_a_row_is_landing_is_applied_once_it_lands() {
+    let mut dragged = 0;
+    for seed in 0..30 {
+        let mut harness = dealt(seed);
+        let stock = harness.stock_point();
+        harness.click(stock);
+        assert!(harness.animating());
+        let Some((from, index, to)) = first_move(&harness) else {
+            continue;
+        };
+        let before = harness.game().clone();
+
+        let grip = harness.landed_grip(from, index);
+        let target = harness.landed_drop_point(to);
+        harness.drag(grip, target);
+        harness.settle();
+
+        let game = harness.game();
+        let moved = before.tableau[from].len() - index;
+        assert_eq!(game.stock.len(), 40);
+        assert_eq!(game.tableau[from].len(), index);
+        assert_eq!(game.tableau[to].len(), before.tableau[to].len() + moved);
+        assert_eq!(harness.history_len(), 2);
+        harness.assert_consistent();
+        dragged += 1;
+    }
+    assert!(dragged > 0);
+}
+
+#[test]
+fn undo_during_a_deal_restores_the_board() {
+    let mut harness = dealt(5);
+    let before = harness.game().clone();
+    let stock = harness.stock_point();
+    harness.click(stock);
+    assert!(harness.animating());
+    harness.undo();
+    harness.settle();
+
+    assert_eq!(harness.game().tableau, before.tableau);
+    assert_eq!(harness.game().stock, before.stock);
+    harness.assert_consistent();
+}
+
+#[test]
+fn stock_click_during_the_opening_deal_deals_once_the_cards_land() {
+    let mut harness = Harness::new();
+    harness.start_deal(Difficulty::Hard, 3);
+    harness.frames(5);
+    assert!(harness.animating());
+    let stock = harness.stock_point();
+    harness.click(stock);
+    harness.settle();
+
+    let game = harness.game();
+    assert_eq!(game.stock.len(), 40);
+    assert_eq!(game.tableau.iter().map(Vec::len).sum::<usize>(), 64);
+    assert_eq!(harness.history_len(), 1);
+    harness.assert_consistent();
+}
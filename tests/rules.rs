@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+
+use proptest::prelude::*;
+
+use learn_rust::debug::check_model;
+use learn_rust::models::{Card, Rank, Suit};
+use learn_rust::resources::{Difficulty, GameMode, SpiderGame};
+use learn_rust::scoring::ScoringScheme;
+use learn_rust::systems::check_completed_sequences;
+
+#[derive(Debug, Clone, Copy)]
+enum Action {
+    Move(usize),
+    Deal,
+    Undo,
+}
+
+fn difficulty() -> impl Strategy<Value = Difficulty> {
+    prop_oneof![
+        Just(Difficulty::Easy),
+        Just(Difficulty::Medium),
+        Just(Difficulty::Hard),
+    ]
+}
+
+fn scoring() -> impl Strategy<Value = ScoringScheme> {
+    proptest::sample::select(ScoringScheme::all().to_vec())
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    prop_oneof![
+        8 => any::<usize>().prop_map(Action::Move),
+        1 => Just(Action::Deal),
+        1 => Just(Action::Undo),
+    ]
+}
+
+fn suit() -> impl Strategy<Value = Suit> {
+    proptest::sample::select(Suit::all().to_vec())
+}
+
+fn card() -> impl Strategy<Value = Card> {
+    (
+        suit(),
+        proptest::sample::select(Rank::all().to_vec()),
+        any::<bool>(),
+    )
+        .prop_map(|(suit, rank, face_up)| Card {
+            suit,
+            rank,
+            face_up,
+        })
+}
+
+fn new_game(difficulty: Difficulty, scoring: ScoringScheme, seed: u64) -> SpiderGame {
+    let mut game = SpiderGame {
+        difficulty,
+        mode: GameMode::Seeded(seed),
+        seed,
+        scoring,
+        score: scoring.starting_score(0),
+        ..SpiderGame::default()
+    };
+    game.deal_tableau();
+    game
+}
+
+fn king_to_ace(suit: Suit) -> Vec<Card> {
+    Rank::all()
+        .into_iter()
+        .rev()
+        .map(|rank| Card {
+            suit,
+            rank,
+            face_up: true,
+        })
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn spider_deck_has_104_cards_in_even_suits(difficulty in difficulty()) {
+        let deck = Card::new_spider_deck(difficulty);
+        prop_assert_eq!(deck.len(), 104);
+        prop_assert!(deck.iter().all(|card| !card.face_up));
+
+        let suits = match difficulty {
+            Difficulty::Easy => 1,
+            Difficulty::Medium => 2,
+            Difficulty::Hard => 4,
+        };
+        let mut counts: HashMap<(Suit, Rank), usize> = HashMap::new();
+        for card in &deck {
+            *counts.entry((card.suit, card.rank)).or_default() += 1;
+        }
+        prop_assert_eq!(counts.len(), suits * 13);
+        prop_assert!(counts.values().all(|&count| count == 8 / suits));
+    }
+
+    #[test]
+    fn shuffled_deck_is_a_seeded_permutation(difficulty in difficulty(), seed in any::<u64>()) {
+        let shuffled = Card::shuffled_spider_deck(difficulty, seed);
+        prop_assert_eq!(&shuffled, &Card::shuffled_spider_deck(difficulty, seed));
+
+        let key = |card: &Card| (card.suit.as_str().to_string(), card.rank.value());
+        let mut sorted = shuffled.clone();
+        sorted.sort_by_key(key);
+        let mut deck = Card::new_spider_deck(difficulty);
+        deck.sort_by_key(key);
+        prop_assert_eq!(sorted, deck);
+    }
+
+    #[test]
+    fn opening_deal_is_54_cards_with_face_up_tops(
+        difficulty in difficulty(),
+        scoring in scoring(),
+        seed in any::<u64>(),
+    ) {
+        let game = new_game(difficulty, scoring, seed);
+        prop_assert_eq!(game.stock.len(), 50);
+        for (column, cards) in game.tableau.iter().enumerate() {
+            prop_assert_eq!(cards.len(), if column < 4 { 6 } else { 5 });
+            prop_assert_eq!(cards.iter().filter(|card| card.face_up).count(), 1);
+        }
+        check_model(&game).map_err(TestCaseError::fail)?;
+    }
+
+    #[test]
+    fn random_play_keeps_the_rules(
+        difficulty in difficulty(),
+        scoring in scoring(),
+        seed in any::<u64>(),
+        actions in proptest::collection::vec(action(), 1..80),
+    ) {
+        let mut game = new_game(difficulty, scoring, seed);
+        let mut history: Vec<SpiderGame> = Vec::new();
+
+        for action in actions {
+            let before = game.clone();
+            match action {
+                Action::Move(pick) => {
+                    let moves = game.legal_moves();
+                    if moves.is_empty() {
+                        continue;
+                    }
+                    let (from, index, to) = moves[pick % moves.len()];
+                    let moved = game.tableau[from].len() - index;
+                    let target_len = game.tableau[to].len();
+                    let result = game.move_cards(from, index, to);
+                    prop_assert!(result.is_some());
+                    prop_assert_eq!(result.unwrap().cards.len(), moved);
+                    prop_assert_eq!(game.tableau[to].len(), target_len + moved);
+                    prop_assert_eq!(game.move_count, before.move_count + 1);
+                    prop_assert_eq!(game.score, before.score - scoring.move_penalty());
+                    history.push(before.clone());
+                }
+                Action::Deal => {
+                    if game.stock.is_empty() {
+                        continue;
+                    }
+                    let dealt = game.deal_row();
+                    prop_assert_eq!(dealt.len(), 10);
+                    prop_assert_eq!(game.stock.len(), before.stock.len() - 10);
+                    prop_assert_eq!(game.score, before.score);
+                    history.push(before.clone());
+                }
+                Action::Undo => {
+                    let Some(previous) = history.pop() else {
+                        continue;
+                    };
+                    game.undo_to(previous.clone());
+                    prop_assert_eq!(&game.tableau, &previous.tableau);
+                    prop_assert_eq!(&game.stock, &previous.stock);
+                    prop_assert_eq!(&game.foundations, &previous.foundations);
+                    prop_assert_eq!(game.undo_count, before.undo_count + 1);
+                    if scoring.undo_counts_as_move() {
+                        prop_assert_eq!(game.score, previous.score - scoring.move_penalty());
+                        prop_assert_eq!(game.move_count, previous.move_count + 1);
+                    } else {
+                        prop_assert_eq!(game.score, previous.score);
+                        prop_assert_eq!(game.move_count, previous.move_count);
+                    }
+                    let mut expected = previous;
+                    expected.undo_count = game.undo_count;
+                    expected.score = game.score;
+                    expected.move_count = game.move_count;
+                    prop_assert_eq!(&game, &expected);
+                    check_model(&game).map_err(TestCaseError::fail)?;
+                    continue;
+                }
+            }
+
+            let score = game.score;
+            let suits = game.foundations.len();
+            let runs = check_completed_sequences(&mut game);
+            prop_assert_eq!(game.foundations.len(), suits + runs.len());
+            prop_assert_eq!(game.score, score + runs.len() as i32 * scoring.suit_bonus());
+            for run in &runs {
+                prop_assert_eq!(&run.cards, &king_to_ace(run.cards[0].suit));
+                prop_assert_eq!(game.foundations[run.foundation], run.cards[0]);
+            }
+            check_model(&game).map_err(TestCaseError::fail)?;
+        }
+    }
+
+    #[test]
+    fn completed_run_is_lifted_off_any_column(
+        prefix in proptest::collection::vec(card(), 0..12),
+        suit in suit(),
+        column in 0usize..10,
+        scoring in scoring(),
+    ) {
+        let mut game = SpiderGame {
+            scoring,
+            ..SpiderGame::default()
+        };
+        game.tableau[column] = prefix.clone();
+        game.tableau[column].extend(king_to_ace(suit));
+        let score = game.score;
+
+        let runs = check_completed_sequences(&mut game);
+        prop_assert_eq!(runs.len(), 1);
+        prop_assert_eq!(runs[0].column, column);
+        prop_assert_eq!(runs[0].start, prefix.len());
+        prop_assert_eq!(&runs[0].cards, &king_to_ace(suit));
+        prop_assert_eq!(game.tableau[column].len(), prefix.len());
+        if let (Some(top), Some(old)) = (game.tableau[column].last(), prefix.last()) {
+            prop_assert!(top.face_up);
+            prop_assert_eq!((top.suit, top.rank), (old.suit, old.rank));
+        }
+        prop_assert_eq!(game.foundations.len(), 1);
+        prop_assert_eq!(game.score, score + scoring.suit_bonus());
+    }
+
+    #[test]
+    fn broken_run_is_left_in_place(
+        suit in suit(),
+        other in suit(),
+        broken in 0usize..13,
+        hide in any::<bool>(),
+    ) {
+        let mut run = king_to_ace(suit);
+        if hide {
+            run[broken].face_up = false;
+        } else if other != suit {
+            run[broken].suit = other;
+        } else {
+            run.remove(broken);
+        }
+        let mut game = SpiderGame::default();
+        game.tableau[0] = run.clone();
+
+        prop_assert!(check_completed_sequences(&mut game).is_empty());
+        prop_assert_eq!(&game.tableau[0], &run);
+        prop_assert!(game.foundations.is_empty());
+    }
+}
+
+#[test]
+fn undoing_a_completed_suit_takes_back_its_bonus() {
+    let scoring = ScoringScheme::Windows;
+    let mut game = SpiderGame {
+        scoring,
+        score: scoring.starting_score(0),
+        ..SpiderGame::default()
+    };
+    let mut run = king_to_ace(Suit::Spades);
+    let ace = run.pop().unwrap();
+    game.tableau[0] = run;
+    game.tableau[1] = vec![ace];
+    let before = game.clone();
+
+    game.move_cards(1, 0, 0).unwrap();
+    assert_eq!(check_completed_sequences(&mut game).len(), 1);
+    assert_eq!(
+        game.score,
+        before.score - scoring.move_penalty() + scoring.suit_bonus()
+    );
+
+    game.undo_to(before.clone());
+    assert!(game.foundations.is_empty());
+    assert_eq!(game.tableau, before.tableau);
+    assert_eq!(game.score, before.score - scoring.move_penalty());
+    assert_eq!(game.move_count, before.move_count + 1);
+}